@@ -0,0 +1,306 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{Cursor, Read, Seek, Write},
+    path::Path,
+};
+
+use calamine::{Data, DataType, Reader, Xlsx};
+use cmm_core::{answer::Answer, cid::CID, control::Control, data::SOCData};
+use roxmltree::Document;
+use zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    ExportError, ImportError, WorkbookVersion, cell, is_output_control,
+    layout::Layout,
+    output_map,
+    sheet::{CellPosition, CellValue, full_calc_on_load, set_cells, set_root_attribute},
+    to_cid,
+};
+
+/// Writes the answers and comments of `data` into a copy of an empty official workbook.
+/// Answers without a counterpart in the workbook (e.g. Not Applicable) are left empty.
+pub fn to_xlsx<P: AsRef<Path>, Q: AsRef<Path>>(
+    template: P,
+    data: &SOCData,
+    output: Q,
+) -> Result<(), ExportError> {
+    let template = std::fs::read(template)?;
+    to_xlsx_writer(Cursor::new(template), data, File::create(output)?)
+}
+
+pub fn to_xlsx_writer<R: Read + Seek, W: Write + Seek>(
+    mut template: R,
+    data: &SOCData,
+    output: W,
+) -> Result<(), ExportError> {
+    let mut bytes = Vec::new();
+    template.read_to_end(&mut bytes)?;
+
+    let mut workbook: Xlsx<_> = Xlsx::new(Cursor::new(bytes.as_slice()))?;
+    let mut zip = ZipArchive::new(Cursor::new(bytes.as_slice()))?;
+    let sheet_paths = sheet_paths(&mut zip)?;
+    let version = WorkbookVersion::detect(&mut zip).unwrap_or_default();
+    let layout = version
+        .layout()
+        .ok_or(ExportError::UnsupportedVersion(version))?;
+
+    // Rewritten parts of the package: path -> xml
+    let mut parts: HashMap<String, String> = HashMap::new();
+
+    let output_range = workbook.worksheet_range(layout.output_sheet)?;
+    let output_rows = output_rows(&output_range, layout);
+    let mut output_cells: BTreeMap<CellPosition, CellValue> = BTreeMap::new();
+
+    // Dropdowns and checkboxes, the selected index is stored in the form control and its linked cell
+    let props: Vec<String> = zip
+        .file_names()
+        .filter(|file| file.starts_with("xl/ctrlProps/"))
+        .map(ToOwned::to_owned)
+        .collect();
+    for prop in props {
+        let xml = read_part(&mut zip, &prop)?;
+        let document = Document::parse(&xml)?;
+        let root = document.root_element();
+        let (Some(output_link), Some(input_link)) =
+            (root.attribute("fmlaLink"), root.attribute("fmlaRange"))
+        else {
+            continue;
+        };
+        let Some(row) = output_link
+            .strip_prefix(layout.output_link)
+            .and_then(|row| row.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let Some(value) = selection(layout, &output_rows, data, row, input_link) else {
+            continue;
+        };
+        parts.insert(prop, set_root_attribute(&xml, "sel", &value.to_string())?);
+        output_cells.insert(
+            (row, layout.output_value_column as u32),
+            CellValue::Number(value as f64),
+        );
+    }
+
+    output_cells.extend(free_text_cells(layout, &output_rows, data));
+
+    let output_path = sheet_paths
+        .get(layout.output_sheet)
+        .ok_or_else(|| ExportError::MissingSheet(layout.output_sheet.to_owned()))?;
+    let xml = read_part(&mut zip, output_path)?;
+    parts.insert(output_path.clone(), set_cells(&xml, &output_cells)?);
+
+    // Comments and/or Remarks block at the end of each assessment sheet
+    for (sheet, domain) in layout.sheets {
+        let range = workbook.worksheet_range(sheet)?;
+        let cells = comment_cells(layout, &range, domain, data);
+        if cells.is_empty() {
+            continue;
+        }
+        let path = sheet_paths
+            .get(*sheet)
+            .ok_or_else(|| ExportError::MissingSheet(sheet.to_string()))?;
+        let xml = read_part(&mut zip, path)?;
+        parts.insert(path.clone(), set_cells(&xml, &cells)?);
+    }
+
+    // Excel has to recalculate all scores with the new values on opening the file.
+    // The calculation chain would reference formulas which were replaced by values.
+    let xml = read_part(&mut zip, "xl/workbook.xml")?;
+    parts.insert(String::from("xl/workbook.xml"), full_calc_on_load(&xml)?);
+    let xml = read_part(&mut zip, "xl/_rels/workbook.xml.rels")?;
+    parts.insert(
+        String::from("xl/_rels/workbook.xml.rels"),
+        remove_elements(&xml, |node| {
+            node.attribute("Type")
+                .is_some_and(|kind| kind.ends_with("/calcChain"))
+        })?,
+    );
+    let xml = read_part(&mut zip, "[Content_Types].xml")?;
+    parts.insert(
+        String::from("[Content_Types].xml"),
+        remove_elements(&xml, |node| {
+            node.attribute("PartName") == Some("/xl/calcChain.xml")
+        })?,
+    );
+
+    let mut writer = ZipWriter::new(output);
+    for i in 0..zip.len() {
+        let file = zip.by_index_raw(i)?;
+        let name = file.name().to_owned();
+        if name == "xl/calcChain.xml" {
+            continue;
+        }
+        match parts.remove(&name) {
+            Some(xml) => {
+                drop(file);
+                writer.start_file(name, SimpleFileOptions::default())?;
+                writer.write_all(xml.as_bytes())?;
+            }
+            None => writer.raw_copy_file(file)?,
+        }
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Selected index of the dropdown which is linked to `row` (1-based) of the output sheet,
+/// None if the answer has no counterpart in the dropdown
+pub(crate) fn selection(
+    layout: &Layout,
+    output_rows: &HashMap<u32, CID>,
+    data: &SOCData,
+    row: u32,
+    input: &str,
+) -> Option<usize> {
+    let control = output_rows.get(&row).and_then(|cid| data.control(cid))?;
+    let (range, value) = output_map(layout, control.answer())?;
+    (range == input).then_some(value)
+}
+
+/// Free text answers are written straight into the output sheet
+pub(crate) fn free_text_cells(
+    layout: &Layout,
+    output_rows: &HashMap<u32, CID>,
+    data: &SOCData,
+) -> BTreeMap<CellPosition, CellValue> {
+    output_rows
+        .iter()
+        .filter_map(|(row, cid)| match data.control(cid)?.answer() {
+            Answer::Any(text) if !text.is_empty() => Some((
+                (*row, layout.output_value_column as u32),
+                CellValue::Text(text.clone()),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Comments and/or Remarks block at the end of an assessment sheet
+pub(crate) fn comment_cells(
+    layout: &Layout,
+    range: &calamine::Range<Data>,
+    domain: &str,
+    data: &SOCData,
+) -> BTreeMap<CellPosition, CellValue> {
+    let (start_row, start_column) = range.start().unwrap_or_default();
+    range
+        .rows()
+        .enumerate()
+        .skip_while(|(_, row)| *cell(row, layout.id_column) != layout.comments_marker)
+        .skip(1)
+        .filter_map(|(i, row)| {
+            let cid = cell(row, layout.comment_id_column)
+                .as_string()
+                .and_then(|id| format!("{domain}.{id}").parse::<CID>().ok())?;
+            let comment = data.control(&cid).and_then(comment_text)?;
+            Some((
+                (
+                    start_row + i as u32 + 1,
+                    start_column + layout.comment_column as u32,
+                ),
+                CellValue::Text(comment),
+            ))
+        })
+        .collect()
+}
+
+/// Comment and discussion thread as a single text
+fn comment_text(control: &Control) -> Option<String> {
+    let text = control
+        .comment()
+        .iter()
+        .filter(|comment| !comment.trim().is_empty())
+        .cloned()
+        .chain(
+            control
+                .comments()
+                .iter()
+                .map(|comment| format!("{}: {}", comment.author(), comment.text())),
+        )
+        .collect::<Vec<_>>()
+        .join("\n");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Excel row (1-based) of each control in the output sheet
+pub(crate) fn output_rows(
+    output_range: &calamine::Range<Data>,
+    layout: &Layout,
+) -> HashMap<u32, CID> {
+    let start_row = output_range.start().unwrap_or_default().0;
+    output_range
+        .rows()
+        .enumerate()
+        .filter(|(_, row)| is_output_control(row, layout))
+        .filter_map(|(i, row)| {
+            let cid = to_cid(&cell(row, layout.output_id_column).to_string()).ok()?;
+            Some((start_row + i as u32 + 1, cid))
+        })
+        .collect()
+}
+
+/// Sheet name -> path of the worksheet xml in the package
+pub(crate) fn sheet_paths<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+) -> Result<HashMap<String, String>, ImportError> {
+    let workbook = read_part(zip, "xl/workbook.xml")?;
+    let relationships = read_part(zip, "xl/_rels/workbook.xml.rels")?;
+    let workbook = Document::parse(&workbook)?;
+    let relationships = Document::parse(&relationships)?;
+
+    let targets: HashMap<&str, &str> = relationships
+        .descendants()
+        .filter(|node| node.has_tag_name("Relationship"))
+        .filter_map(|node| Some((node.attribute("Id")?, node.attribute("Target")?)))
+        .collect();
+
+    Ok(workbook
+        .descendants()
+        .filter(|node| node.has_tag_name("sheet"))
+        .filter_map(|sheet| {
+            let id = sheet
+                .attributes()
+                .find(|attribute| attribute.name() == "id")?;
+            let target = targets.get(id.value())?;
+            let path = match target.strip_prefix('/') {
+                Some(absolute) => absolute.to_owned(),
+                None => format!("xl/{target}"),
+            };
+            Some((sheet.attribute("name")?.to_owned(), path))
+        })
+        .collect())
+}
+
+pub(crate) fn read_part<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<String, ImportError> {
+    let mut part = zip.by_name(name)?;
+    let mut string = String::new();
+    part.read_to_string(&mut string)?;
+    Ok(string)
+}
+
+fn remove_elements<F: Fn(&roxmltree::Node) -> bool>(
+    xml: &str,
+    predicate: F,
+) -> Result<String, ExportError> {
+    let document = Document::parse(xml)?;
+    let mut ranges: Vec<_> = document
+        .descendants()
+        .filter(|node| node.is_element() && predicate(node))
+        .map(|node| node.range())
+        .collect();
+    ranges.sort_by_key(|range| range.start);
+
+    let mut result = String::with_capacity(xml.len());
+    let mut cursor = 0;
+    for range in ranges {
+        result.push_str(&xml[cursor..range.start]);
+        cursor = range.end;
+    }
+    result.push_str(&xml[cursor..]);
+    Ok(result)
+}
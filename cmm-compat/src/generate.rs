@@ -0,0 +1,430 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Read, Seek},
+    path::Path,
+};
+
+use calamine::{Data, DataType, Range, Reader, Xlsx, open_workbook};
+use cmm_core::{
+    cid::{CID, Domain},
+    schema::Schema,
+};
+use roxmltree::Document;
+use serde_json::{Map, Value, json};
+use zip::ZipArchive;
+
+use crate::{
+    ImportError, WorkbookVersion, cell,
+    export::{read_part, sheet_paths},
+    form_controls, is_nist_mapping, is_output_id,
+    layout::{InputKind, Layout},
+    profile::same,
+    sheet::parse_reference,
+    to_cid,
+};
+
+/// Row of a control in the output sheet
+struct OutputRow {
+    control_type: String,
+    nist_only: bool,
+    input: Option<InputKind>,
+}
+
+/// Extracts the schema of a blank official workbook.
+/// Everything which is not part of the workbook (profile keys, recommendations and
+/// dependencies) is taken from `previous`, e.g. the schema of the last release.
+pub fn schema_from_xlsx<P: AsRef<Path>>(
+    path: P,
+    previous: Option<&Schema>,
+) -> Result<Schema, ImportError> {
+    let mut zip = ZipArchive::new(File::open(&path)?)?;
+    let version = WorkbookVersion::detect(&mut zip).unwrap_or_default();
+    let layout = version
+        .layout()
+        .ok_or(ImportError::UnsupportedVersion(version))?;
+
+    let mut workbook: Xlsx<_> = open_workbook(&path)?;
+    let output = workbook.worksheet_range(layout.output_sheet)?;
+    let output_rows = output_rows(&output, &mut zip, layout)?;
+
+    let mut aspects: Map<String, Value> = Map::new();
+    let mut control_schemas: Map<String, Value> = Map::new();
+    for (sheet, domain) in layout.sheets {
+        let range = workbook.worksheet_range(sheet)?;
+        let Ok(parsed_domain) = domain.parse::<Domain>() else {
+            continue;
+        };
+
+        let titles = aspects
+            .entry(domain.to_string())
+            .or_insert_with(|| Value::Array(Vec::new()));
+        if let Value::Array(titles) = titles {
+            let index = titles.len();
+            let title = aspect_title(&range, layout, index + 1)
+                .or_else(|| {
+                    previous?
+                        .aspects(&parsed_domain)
+                        .get(index)
+                        .map(|title| title.to_string())
+                })
+                .unwrap_or_else(|| sheet.to_string());
+            titles.push(Value::String(title));
+        }
+
+        for row in range
+            .rows()
+            .skip(layout.header_rows)
+            .take_while(|row| *cell(row, layout.id_column) != layout.comments_marker)
+        {
+            let text = cell(row, layout.id_column).to_string();
+            let text = text.trim_start();
+            let Some(id) = text
+                .split_whitespace()
+                .next()
+                .filter(|id| id.starts_with(|char: char| char.is_ascii_digit()))
+            else {
+                continue;
+            };
+            let Ok(cid) = format!("{domain}.{id}").parse::<CID>() else {
+                continue;
+            };
+            let schema = control_schema(
+                row,
+                &text[id.len()..],
+                layout,
+                output_rows.get(&cid),
+                previous,
+                &cid,
+            );
+            control_schemas.insert(cid.to_string(), schema);
+        }
+    }
+
+    let profile = profile(&mut zip, &mut workbook, layout, previous)?;
+
+    Ok(serde_json::from_value(json!({
+        "aspects": aspects,
+        "control_schemas": control_schemas,
+        "profile": profile,
+    }))?)
+}
+
+/// Title of the aspect in the header of its sheet, e.g. "1. Business Drivers"
+fn aspect_title(range: &Range<Data>, layout: &Layout, aspect_id: usize) -> Option<String> {
+    range
+        .rows()
+        .take(layout.header_rows)
+        .flat_map(|row| row.iter())
+        .filter_map(|cell| cell.as_string())
+        .find_map(|text| {
+            let title = text
+                .trim()
+                .strip_prefix(&aspect_id.to_string())?
+                .trim_start_matches('.');
+            title
+                .starts_with(char::is_whitespace)
+                .then(|| title.trim().to_owned())
+        })
+}
+
+/// Control type, NIST marker and input of every control in the output sheet
+fn output_rows<R: Read + Seek>(
+    output: &Range<Data>,
+    zip: &mut ZipArchive<R>,
+    layout: &Layout,
+) -> Result<HashMap<CID, OutputRow>, ImportError> {
+    let inputs: HashMap<u32, InputKind> = form_controls(zip, layout)?
+        .into_iter()
+        .filter_map(|form_control| {
+            Some((
+                form_control.output_row?,
+                layout.input_kind(&form_control.input)?,
+            ))
+        })
+        .collect();
+
+    let start_row = output.start().unwrap_or_default().0;
+    Ok(output
+        .rows()
+        .enumerate()
+        .filter(|(_, row)| is_output_id(row, layout))
+        .filter_map(|(i, row)| {
+            let cid = to_cid(&cell(row, layout.output_id_column).to_string()).ok()?;
+            Some((
+                cid,
+                OutputRow {
+                    control_type: cell(row, layout.output_type_column).to_string(),
+                    nist_only: is_nist_mapping(row, layout),
+                    input: inputs.get(&(start_row + i as u32)).copied(),
+                },
+            ))
+        })
+        .collect())
+}
+
+/// Schema of a control row: the title follows the id, guidances and remarks are the texts
+/// after the title, one guidance per selectable value
+fn control_schema(
+    row: &[Data],
+    title: &str,
+    layout: &Layout,
+    output_row: Option<&OutputRow>,
+    previous: Option<&Schema>,
+    cid: &CID,
+) -> Value {
+    let mut texts = row
+        .iter()
+        .skip(layout.id_column + 1)
+        .filter_map(|cell| cell.as_string())
+        .map(|text| text.trim().to_owned())
+        .filter(|text| !text.is_empty());
+    let title = match title.trim() {
+        "" => texts.next().unwrap_or_default(),
+        title => title.to_owned(),
+    };
+    let texts: Vec<String> = texts.collect();
+
+    let (control_type, options) = match output_row {
+        Some(OutputRow {
+            input: Some(input), ..
+        }) => match input {
+            InputKind::DetailedOptional => ("DetailedOptional", 6),
+            InputKind::Detailed => ("Detailed", 5),
+            InputKind::Occurence => ("Occurence", 5),
+            InputKind::Satisfaction => ("Satisfaction", 5),
+            InputKind::Bool => ("Bool", 0),
+        },
+        Some(output_row) if ["C", "M"].contains(&output_row.control_type.as_str()) => {
+            ("ScoredSectionTitle", 0)
+        }
+        Some(_) => ("Any", 0),
+        None => ("Title", 0),
+    };
+    let guidances = if options > 0 && texts.len() >= options {
+        &texts[..options]
+    } else {
+        &[]
+    };
+
+    let mut schema = json!({
+        "title": title,
+        "control_type": control_type,
+    });
+    if !guidances.is_empty() {
+        schema["guidances"] = json!(guidances);
+    }
+    if let Some(remarks) = texts.get(guidances.len()) {
+        schema["remarks"] = json!(remarks);
+    }
+    if output_row.is_some_and(|output_row| output_row.nist_only) {
+        schema["nist_only"] = json!(true);
+    }
+
+    // Additions which are not part of the workbook
+    if let Some(Ok(Value::Object(previous))) = previous
+        .and_then(|previous| previous.control_schema(cid))
+        .map(serde_json::to_value)
+    {
+        for key in ["recommendations", "depends_on"] {
+            if let Some(value) = previous.get(key) {
+                schema[key] = value.clone();
+            }
+        }
+    }
+    schema
+}
+
+/// Profile questions with an input validation on the profile sheet. Questions without a
+/// validation (free text) can not be recognized, those of `previous` are kept.
+fn profile<R: Read + Seek, RS: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    workbook: &mut Xlsx<RS>,
+    layout: &Layout,
+    previous: Option<&Schema>,
+) -> Result<Map<String, Value>, ImportError> {
+    let mut profile = Map::new();
+    let Some(path) = sheet_paths(zip)?.remove(layout.profile_sheet) else {
+        return Ok(profile);
+    };
+    let range = workbook.worksheet_range(layout.profile_sheet)?;
+    let xml = read_part(zip, &path)?;
+    let document = Document::parse(&xml)?;
+
+    for validation in document
+        .descendants()
+        .filter(|node| node.has_tag_name("dataValidation"))
+    {
+        let Some((row, column)) = validation
+            .attribute("sqref")
+            .and_then(|sqref| sqref.split([' ', ':']).next())
+            .and_then(parse_reference)
+        else {
+            continue;
+        };
+        // The question is the nearest text left of the input
+        let Some(question) = (0..column).rev().find_map(|column| {
+            range
+                .get_value((row - 1, column))
+                .and_then(|cell| cell.as_string())
+                .filter(|text| !text.trim().is_empty())
+        }) else {
+            continue;
+        };
+        let formula = validation
+            .children()
+            .find(|node| node.has_tag_name("formula1"))
+            .and_then(|node| node.text())
+            .unwrap_or_default();
+
+        let question_type = match validation.attribute("type") {
+            Some("list") => {
+                let items = list_items(workbook, formula);
+                let yes_no = items.len() == 2
+                    && items
+                        .iter()
+                        .all(|item| same(item, "Yes") || same(item, "No"));
+                if yes_no {
+                    json!({"type": "YesNo"})
+                } else {
+                    json!({"type": "Select", "items": items})
+                }
+            }
+            Some("date") => json!({"type": "Date"}),
+            Some("whole" | "decimal") => json!({"type": "Number"}),
+            _ => json!({"type": "Text"}),
+        };
+
+        let known = previous.and_then(|previous| {
+            previous
+                .profile()
+                .iter()
+                .find(|(_, known)| same(known.question(), &question))
+        });
+        let (key, mut entry) = match known.map(|(key, known)| (key, serde_json::to_value(known))) {
+            Some((key, Ok(Value::Object(known)))) => (key.clone(), known),
+            _ => {
+                let short = question.trim().trim_end_matches(['?', ':']).to_owned();
+                (
+                    key(&short),
+                    Map::from_iter([
+                        (String::from("short"), json!(short)),
+                        (String::from("category"), json!("Organization")),
+                    ]),
+                )
+            }
+        };
+        entry.insert(String::from("question"), json!(question.trim()));
+        entry.remove("items");
+        if let Value::Object(question_type) = question_type {
+            entry.extend(question_type);
+        }
+        profile.insert(key, Value::Object(entry));
+    }
+
+    for (key, question) in previous.iter().flat_map(|previous| previous.profile()) {
+        if !profile.contains_key(key)
+            && let Ok(question) = serde_json::to_value(question)
+        {
+            profile.insert(key.clone(), question);
+        }
+    }
+    Ok(profile)
+}
+
+/// Items of a list validation, either inline ("a,b") or a reference to a range of cells
+fn list_items<RS: Read + Seek>(workbook: &mut Xlsx<RS>, formula: &str) -> Vec<String> {
+    if let Some(inline) = formula
+        .strip_prefix('"')
+        .and_then(|formula| formula.strip_suffix('"'))
+    {
+        return inline
+            .split(',')
+            .map(|item| item.trim().to_owned())
+            .collect();
+    }
+    let Some((sheet, reference)) = formula.rsplit_once('!') else {
+        return Vec::new();
+    };
+    let sheet = sheet.trim_matches('\'');
+    let (start, end) = reference.split_once(':').unwrap_or((reference, reference));
+    let (Some(start), Some(end), Ok(range)) = (
+        parse_reference(start),
+        parse_reference(end),
+        workbook.worksheet_range(sheet),
+    ) else {
+        return Vec::new();
+    };
+    range
+        .range((start.0 - 1, start.1), (end.0 - 1, end.1))
+        .cells()
+        .filter_map(|(_, _, cell)| cell.as_string())
+        .map(|item| item.trim().to_owned())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Profile key of a new question, e.g. "SOC size (FTE)" -> "soc_size_fte"
+fn key(short: &str) -> String {
+    short
+        .split(|char: char| !char.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key() {
+        assert_eq!(key("SOC size (FTE)"), "soc_size_fte");
+        assert_eq!(key("Years of SOC operations"), "years_of_soc_operations");
+    }
+
+    #[test]
+    fn test_control_schema() {
+        let row = [
+            Data::Empty,
+            Data::String(String::from(
+                "1.1 Have you identified the main business drivers?",
+            )),
+            Data::Empty,
+            Data::String(String::from("unknown")),
+            Data::String(String::from("basic")),
+            Data::String(String::from("some")),
+            Data::String(String::from("most")),
+            Data::String(String::from("all")),
+            Data::String(String::from("e.g. risk reduction")),
+        ];
+        let layout = WorkbookVersion::default().layout().unwrap();
+        let output_row = OutputRow {
+            control_type: String::from("M"),
+            nist_only: false,
+            input: Some(InputKind::Detailed),
+        };
+        let cid: CID = "Business.1.1".parse().unwrap();
+        assert_eq!(
+            control_schema(
+                &row,
+                " Have you identified the main business drivers?",
+                layout,
+                Some(&output_row),
+                None,
+                &cid
+            ),
+            json!({
+                "title": "Have you identified the main business drivers?",
+                "control_type": "Detailed",
+                "guidances": ["unknown", "basic", "some", "most", "all"],
+                "remarks": "e.g. risk reduction",
+            })
+        );
+        assert_eq!(
+            control_schema(&row[..2], "", layout, None, None, &cid)["control_type"],
+            json!("Title")
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use std::{
+    fmt::Display,
+    io::{Read, Seek},
+};
+
+use cmm_core::answer::{Answer, Detailed, DetailedOptional, Occurence, Satisfaction};
+use roxmltree::Document;
+use zip::ZipArchive;
+
+/// Edition of the official workbook, the NIST variant contains the NIST only controls
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Edition {
+    #[default]
+    Basic,
+    Advanced,
+    Nist,
+}
+
+/// SOC-CMM release and edition of a workbook, e.g. 2.3.4 basic
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct WorkbookVersion {
+    version: (u32, u32, u32),
+    edition: Edition,
+}
+
+/// Answer type behind an input range of the `_Input` sheet
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum InputKind {
+    DetailedOptional,
+    Detailed,
+    Bool,
+    Occurence,
+    Satisfaction,
+}
+
+/// Where the data of a workbook release is located
+pub(crate) struct Layout {
+    /// Assessment sheets with the domain of their controls
+    pub(crate) sheets: &'static [(&'static str, &'static str)],
+    /// Rows of an assessment sheet before the first control
+    pub(crate) header_rows: usize,
+    /// Column of the control id in the assessment rows, e.g. "1.2 Charter"
+    pub(crate) id_column: usize,
+    /// First cell of the comments block at the end of each assessment sheet
+    pub(crate) comments_marker: &'static str,
+    pub(crate) comment_id_column: usize,
+    pub(crate) comment_column: usize,
+    pub(crate) output_sheet: &'static str,
+    pub(crate) output_id_column: usize,
+    /// "C" for capabilities and "M" for maturity controls
+    pub(crate) output_type_column: usize,
+    pub(crate) output_value_column: usize,
+    /// Prefix of the cell linked to a form control, followed by the row
+    pub(crate) output_link: &'static str,
+    /// Marks the rows which are only used by the NIST mapping
+    pub(crate) nist_column: usize,
+    pub(crate) inputs: &'static [(&'static str, InputKind)],
+    /// General information about the assessment and the organisation
+    pub(crate) profile_sheet: &'static str,
+    /// Column of the scores computed by the workbook. Score rows are labelled in the id column
+    /// with the aspect, e.g. "B 1", or the domain, e.g. "Business", the type column tells
+    /// maturity ("M") from capability ("C") scores.
+    pub(crate) score_column: usize,
+}
+
+const LAYOUT_2_3: Layout = Layout {
+    sheets: &[
+        ("Business - BSD", "Business"),
+        ("Business - CST", "Business"),
+        ("Business - CHT", "Business"),
+        ("Business - GOV", "Business"),
+        ("Business - PRV", "Business"),
+        ("People - EMP", "People"),
+        ("People - R&H", "People"),
+        ("People - PEM", "People"),
+        ("People - KNM", "People"),
+        ("People - T&E", "People"),
+        ("Process - MGT", "Process"),
+        ("Process - O&F", "Process"),
+        ("Process - RPT", "Process"),
+        ("Process - UCM", "Process"),
+        ("Process - DTE", "Process"),
+        ("Technology - SIM", "Technology"),
+        ("Technology - NDR", "Technology"),
+        ("Technology - EDR", "Technology"),
+        ("Technology - A&O", "Technology"),
+        ("Services - SCM", "Services"),
+        ("Services - SIM", "Services"),
+        ("Services - A&F", "Services"),
+        ("Services - THR", "Services"),
+        ("Services - HNT", "Services"),
+        ("Services - VUL", "Services"),
+        ("Services - LOG", "Services"),
+    ],
+    header_rows: 9,
+    id_column: 1,
+    comments_marker: "Comments and/or Remarks",
+    comment_id_column: 11,
+    comment_column: 13,
+    output_sheet: "_Output",
+    output_id_column: 0,
+    output_type_column: 2,
+    output_value_column: 3,
+    output_link: "_Output!$D$",
+    nist_column: 13,
+    inputs: &[
+        ("_Input!$C$13:$C$18", InputKind::DetailedOptional),
+        ("_Input!$C$13:$C$17", InputKind::Detailed),
+        ("_Input!$C$3:$C$4", InputKind::Bool),
+        ("_Input!$C$39:$C$43", InputKind::Occurence),
+        ("_Input!$C$45:$C$49", InputKind::Satisfaction),
+    ],
+    profile_sheet: "Profile",
+    score_column: 12,
+};
+
+impl WorkbookVersion {
+    pub fn new(version: (u32, u32, u32), edition: Edition) -> Self {
+        Self { version, edition }
+    }
+
+    pub fn version(&self) -> (u32, u32, u32) {
+        self.version
+    }
+
+    pub fn edition(&self) -> Edition {
+        self.edition
+    }
+
+    /// Reads the release from the document properties of the workbook (xlsx or ods),
+    /// e.g. the title "SOC-CMM 2.3.4 - Advanced". The texts of the sheets are not searched,
+    /// questions and remarks may mention other releases.
+    pub fn detect<R: Read + Seek>(zip: &mut ZipArchive<R>) -> Option<Self> {
+        // docProps/core.xml of xlsx and meta.xml of OpenDocument
+        let properties = ["docProps/core.xml", "meta.xml"];
+        let fields = ["title", "subject", "description", "keywords", "keyword"];
+        properties.into_iter().find_map(|name| {
+            let mut xml = String::new();
+            zip.by_name(name).ok()?.read_to_string(&mut xml).ok()?;
+            let document = Document::parse(&xml).ok()?;
+            document
+                .descendants()
+                .filter(|node| fields.contains(&node.tag_name().name()))
+                .filter_map(|node| node.text())
+                .find_map(Self::parse)
+        })
+    }
+
+    /// Finds the release in a text like "SOC-CMM version 2.3.4 basic"
+    pub fn parse(text: &str) -> Option<Self> {
+        let lowercase = text.to_lowercase();
+        let start = lowercase.find("soc-cmm")? + "soc-cmm".len();
+        let version = lowercase[start..]
+            .split(|char: char| !(char.is_ascii_digit() || char == '.'))
+            .find(|word| word.contains('.'))?;
+        let mut numbers = version
+            .trim_matches('.')
+            .split('.')
+            .map(|number| number.parse::<u32>().ok());
+        let major = numbers.next()??;
+        let minor = numbers.next()??;
+        let patch = numbers.next().flatten().unwrap_or_default();
+
+        let edition = if lowercase.contains("nist") {
+            Edition::Nist
+        } else if lowercase.contains("advanced") {
+            Edition::Advanced
+        } else {
+            Edition::Basic
+        };
+        Some(Self::new((major, minor, patch), edition))
+    }
+
+    /// Layout descriptor of this release, None if the release is not supported.
+    /// Older releases are numbered differently, their answers would end up on other controls.
+    pub(crate) fn layout(&self) -> Option<&'static Layout> {
+        match self.version {
+            (2, 3, _) => Some(&LAYOUT_2_3),
+            _ => None,
+        }
+    }
+}
+
+impl Default for WorkbookVersion {
+    /// The release the scheme was created from
+    fn default() -> Self {
+        Self::new((2, 3, 4), Edition::Basic)
+    }
+}
+
+impl Display for WorkbookVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (major, minor, patch) = self.version;
+        write!(f, "{major}.{minor}.{patch} {}", self.edition)
+    }
+}
+
+impl Display for Edition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Edition::Basic => write!(f, "basic"),
+            Edition::Advanced => write!(f, "advanced"),
+            Edition::Nist => write!(f, "NIST"),
+        }
+    }
+}
+
+impl Layout {
+    pub(crate) fn input_kind(&self, input: &str) -> Option<InputKind> {
+        self.inputs
+            .iter()
+            .find(|(range, _)| *range == input)
+            .map(|(_, kind)| *kind)
+    }
+
+    pub(crate) fn input_range(&self, kind: InputKind) -> Option<&'static str> {
+        self.inputs
+            .iter()
+            .find(|(_, input_kind)| *input_kind == kind)
+            .map(|(range, _)| *range)
+    }
+}
+
+impl InputKind {
+    pub(crate) fn of(answer: &Answer) -> Option<Self> {
+        match answer {
+            Answer::DetailedOptional(_) => Some(InputKind::DetailedOptional),
+            Answer::Detailed(_) => Some(InputKind::Detailed),
+            Answer::Bool(_) => Some(InputKind::Bool),
+            Answer::Occurence(_) => Some(InputKind::Occurence),
+            Answer::Satisfaction(_) => Some(InputKind::Satisfaction),
+            Answer::Any(_) => None,
+        }
+    }
+
+    /// Control type in the output sheet, Bool is used by both
+    pub(crate) fn control_type(&self) -> Option<&'static str> {
+        match self {
+            InputKind::DetailedOptional => Some("C"),
+            InputKind::Detailed | InputKind::Occurence | InputKind::Satisfaction => Some("M"),
+            InputKind::Bool => None,
+        }
+    }
+
+    /// Answer of the selected index of a form control
+    pub(crate) fn answer(&self, value: usize) -> Answer {
+        match self {
+            InputKind::DetailedOptional => {
+                Answer::DetailedOptional(DetailedOptional::from_repr(value).unwrap_or_default())
+            }
+            InputKind::Detailed => Answer::Detailed(Detailed::from_repr(value).unwrap_or_default()),
+            InputKind::Bool => Answer::Bool(value > 1),
+            InputKind::Occurence => {
+                Answer::Occurence(Occurence::from_repr(value).unwrap_or_default())
+            }
+            InputKind::Satisfaction => {
+                Answer::Satisfaction(Satisfaction::from_repr(value).unwrap_or_default())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            WorkbookVersion::parse("SOC-CMM 2.3.4 basic"),
+            Some(WorkbookVersion::new((2, 3, 4), Edition::Basic))
+        );
+        assert_eq!(
+            WorkbookVersion::parse("SOC-CMM version 2.2 - Advanced"),
+            Some(WorkbookVersion::new((2, 2, 0), Edition::Advanced))
+        );
+        assert_eq!(
+            WorkbookVersion::parse("soc-cmm-2.3.1-nist.xlsx"),
+            Some(WorkbookVersion::new((2, 3, 1), Edition::Nist))
+        );
+        assert_eq!(WorkbookVersion::parse("SOC-CMM"), None);
+        assert_eq!(WorkbookVersion::parse("Version 2.3.4"), None);
+    }
+
+    #[test]
+    fn test_layout() {
+        assert!(WorkbookVersion::default().layout().is_some());
+        // Not checked against a 2.2 workbook, its controls are numbered differently
+        assert!(
+            WorkbookVersion::new((2, 2, 0), Edition::Advanced)
+                .layout()
+                .is_none()
+        );
+        assert!(
+            WorkbookVersion::new((2, 1, 0), Edition::Basic)
+                .layout()
+                .is_none()
+        );
+        assert_eq!(
+            LAYOUT_2_3.input_kind("_Input!$C$39:$C$43"),
+            Some(InputKind::Occurence)
+        );
+        assert_eq!(
+            LAYOUT_2_3.input_range(InputKind::Bool),
+            Some("_Input!$C$3:$C$4")
+        );
+    }
+}
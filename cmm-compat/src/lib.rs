@@ -1,40 +1,227 @@
 use std::{
     collections::HashMap,
     fs::File,
-    io::{BufReader, Read},
+    io::{Cursor, Read, Seek},
     path::Path,
 };
 
-use anyhow::Ok;
-use calamine::{Data, DataType, Reader, ToCellDeserializer, Xlsx, open_workbook};
+use calamine::{Data, DataType, Reader, ToCellDeserializer, Xlsx};
 use cmm_core::{
-    answer::{Answer, Detailed, DetailedOptional, Occurence, Satisfaction},
+    CmmError,
+    answer::{Answer, Detailed, DetailedOptional},
     cid::CID,
     control::Control,
     data::SOCData,
+    schema::Schema,
 };
+use layout::{InputKind, Layout};
+use sheet::parse_reference;
+use thiserror::Error;
 
 use roxmltree::Document;
+use zip::ZipArchive;
 
-pub fn from_xlsx<P: AsRef<Path>>(path: P) -> anyhow::Result<SOCData> {
-    let mut workbook: Xlsx<_> = open_workbook(&path)?;
-    let mut controls = comments(&mut workbook)?;
+mod export;
+mod generate;
+mod layout;
+mod ods;
+mod profile;
+mod report;
+mod sheet;
+mod table;
+mod verify;
 
-    let output = workbook.worksheet_range("_Output")?;
+pub use export::{to_xlsx, to_xlsx_writer};
+pub use generate::schema_from_xlsx;
+pub use layout::{Edition, WorkbookVersion};
+pub use ods::{from_ods, from_ods_reader, to_ods, to_ods_writer};
+pub use report::{ImportReport, ImportWarning};
+pub use verify::{Parity, ScoreKind, verify_ods, verify_xlsx, verify_xlsx_reader};
 
-    extend_answer_from_output(&output, &mut controls);
-    extend_answer_from_form_controls(&mut controls, &output, path)?;
-    nist_compat(&mut controls);
+/// Control while importing, titles never get an answer and are dropped
+struct Entry {
+    answer: Option<Answer>,
+    comment: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("Workbook is missing the sheet {0}")]
+    MissingSheet(String),
+    #[error("SOC-CMM {0} workbooks are not supported")]
+    UnsupportedVersion(WorkbookVersion),
+    #[error(transparent)]
+    Xlsx(calamine::XlsxError),
+    #[error(transparent)]
+    Ods(calamine::OdsError),
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+    #[error(transparent)]
+    Xml(#[from] roxmltree::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Extracted schema is invalid: {0}")]
+    InvalidSchema(#[from] serde_json::Error),
+}
 
-    // let controls = controls
-    //     .into_iter()
-    //     .filter(|(_cid, control)| !control.is_default())
-    //     .collect();
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("Cannot read the template: {0}")]
+    Template(#[from] ImportError),
+    #[error("Template is missing the sheet {0}")]
+    MissingSheet(String),
+    #[error("SOC-CMM {0} workbooks are not supported")]
+    UnsupportedVersion(WorkbookVersion),
+    #[error("Cell {0} holds the formula shared by other cells and cannot be overwritten")]
+    SharedFormula(String),
+    #[error("Malformed template: {0}")]
+    Malformed(String),
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+    #[error(transparent)]
+    Xml(#[from] roxmltree::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
 
-    Ok(SOCData::from_map(controls))
+impl From<calamine::XlsxError> for ExportError {
+    fn from(error: calamine::XlsxError) -> Self {
+        match ImportError::from(error) {
+            ImportError::MissingSheet(sheet) => ExportError::MissingSheet(sheet),
+            error => ExportError::Template(error),
+        }
+    }
 }
 
-fn nist_compat(controls: &mut HashMap<CID, Control>) {
+impl From<calamine::OdsError> for ExportError {
+    fn from(error: calamine::OdsError) -> Self {
+        match ImportError::from(error) {
+            ImportError::MissingSheet(sheet) => ExportError::MissingSheet(sheet),
+            error => ExportError::Template(error),
+        }
+    }
+}
+
+impl From<calamine::XlsxError> for ImportError {
+    fn from(error: calamine::XlsxError) -> Self {
+        match error {
+            calamine::XlsxError::WorksheetNotFound(sheet) => ImportError::MissingSheet(sheet),
+            error => ImportError::Xlsx(error),
+        }
+    }
+}
+
+impl From<calamine::OdsError> for ImportError {
+    fn from(error: calamine::OdsError) -> Self {
+        match error {
+            calamine::OdsError::WorksheetNotFound(sheet) => ImportError::MissingSheet(sheet),
+            error => ImportError::Ods(error),
+        }
+    }
+}
+
+/// Imports an assessment, everything which could not be mapped is skipped and listed in the report.
+/// The profile answers are validated against the profile questions of `schema`.
+pub fn from_xlsx<P: AsRef<Path>>(
+    path: P,
+    schema: &Schema,
+) -> Result<(SOCData, ImportReport), ImportError> {
+    from_xlsx_reader(File::open(path)?, schema)
+}
+
+/// Imports an assessment from the bytes of a workbook, e.g. an upload in the browser
+pub fn from_xlsx_reader<R: Read + Seek>(
+    mut reader: R,
+    schema: &Schema,
+) -> Result<(SOCData, ImportReport), ImportError> {
+    // The workbook is read twice, by calamine and for the form controls
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let mut zip = ZipArchive::new(Cursor::new(bytes.as_slice()))?;
+    let version = WorkbookVersion::detect(&mut zip);
+    let workbook: Xlsx<_> = Xlsx::new(Cursor::new(bytes.as_slice()))?;
+    import(
+        workbook,
+        version,
+        |layout| form_controls(&mut zip, layout),
+        schema,
+    )
+}
+
+/// Import of all workbook formats, only the form controls are read by the caller
+pub(crate) fn import<RS, W, F>(
+    mut workbook: W,
+    version: Option<WorkbookVersion>,
+    form_controls: F,
+    schema: &Schema,
+) -> Result<(SOCData, ImportReport), ImportError>
+where
+    RS: Read + Seek,
+    W: Reader<RS>,
+    ImportError: From<W::Error>,
+    F: FnOnce(&Layout) -> Result<Vec<FormControl>, ImportError>,
+{
+    let mut report = ImportReport::default();
+    let version = version.unwrap_or_else(|| {
+        report.push(ImportWarning::UnknownVersion);
+        WorkbookVersion::default()
+    });
+    let layout = version
+        .layout()
+        .ok_or(ImportError::UnsupportedVersion(version))?;
+    report.set_version(version);
+
+    let mut controls = comments(&mut workbook, layout, &mut report)?;
+
+    let output = workbook.worksheet_range(layout.output_sheet)?;
+    let form_controls = form_controls(layout)?;
+    let entries = if form_controls
+        .iter()
+        .any(|form_control| form_control.linkage == Linkage::Text)
+    {
+        input_entries(&mut workbook, layout)
+    } else {
+        HashMap::new()
+    };
+
+    extend_answer_from_output(&output, layout, &mut controls, &mut report);
+    extend_answer_from_form_controls(
+        &mut controls,
+        &output,
+        form_controls,
+        &entries,
+        layout,
+        &mut report,
+    );
+    if version.edition() != Edition::Nist {
+        nist_compat(&mut controls, &mut report);
+    }
+
+    let controls = controls
+        .into_iter()
+        .filter_map(|(cid, entry)| Some((cid, Control::new(entry.answer?, entry.comment))))
+        .collect();
+
+    let mut soc_data = SOCData::from_map(controls);
+    match workbook
+        .worksheet_range(layout.profile_sheet)
+        .map_err(ImportError::from)
+    {
+        Ok(range) => {
+            for (key, value) in profile::profile(&range, schema, &mut report) {
+                soc_data.set_profile_answer(key, value);
+            }
+        }
+        Err(ImportError::MissingSheet(sheet)) => report.push(ImportWarning::MissingProfile(sheet)),
+        Err(error) => return Err(error),
+    }
+
+    Ok((soc_data, report))
+}
+
+/// NIST only controls are not part of the basic and advanced workbook, nist_only itself is part of the schema
+fn nist_compat(controls: &mut HashMap<CID, Entry>, report: &mut ImportReport) {
     let compat = Vec::from([
         (
             "Services.4.15.30",
@@ -75,214 +262,356 @@ fn nist_compat(controls: &mut HashMap<CID, Control>) {
     ]);
 
     for (cid, answer) in compat {
-        let control = controls
-            .get_mut(&cid.parse().unwrap())
-            .expect("Compat CID not in controls");
-        control.set_answer(answer);
-        control.set_nist_only(cid != "People.2.2.14");
+        let Ok(cid) = cid.parse::<CID>() else {
+            continue;
+        };
+        match controls.get_mut(&cid) {
+            Some(control) => control.answer = Some(answer),
+            None => report.push(ImportWarning::MissingControl(cid)),
+        }
     }
 }
 
-// Create all Controls from the sheets including comments - without answer type
-fn comments(workbook: &mut Xlsx<BufReader<File>>) -> anyhow::Result<HashMap<CID, Control>> {
-    let sheets = vec![
-        ("Business - BSD", "Business"),
-        ("Business - CST", "Business"),
-        ("Business - CHT", "Business"),
-        ("Business - GOV", "Business"),
-        ("Business - PRV", "Business"),
-        ("People - EMP", "People"),
-        ("People - R&H", "People"),
-        ("People - PEM", "People"),
-        ("People - KNM", "People"),
-        ("People - T&E", "People"),
-        ("Process - MGT", "Process"),
-        ("Process - O&F", "Process"),
-        ("Process - RPT", "Process"),
-        ("Process - UCM", "Process"),
-        ("Process - DTE", "Process"),
-        ("Technology - SIM", "Technology"),
-        ("Technology - NDR", "Technology"),
-        ("Technology - EDR", "Technology"),
-        ("Technology - A&O", "Technology"),
-        ("Services - SCM", "Services"),
-        ("Services - SIM", "Services"),
-        ("Services - A&F", "Services"),
-        ("Services - THR", "Services"),
-        ("Services - HNT", "Services"),
-        ("Services - VUL", "Services"),
-        ("Services - LOG", "Services"),
-    ];
+/// Cell of a row, rows of a narrower range count as empty
+pub(crate) fn cell(row: &[Data], column: usize) -> &Data {
+    row.get(column).unwrap_or(&Data::Empty)
+}
 
+// Create all Controls from the sheets including comments - without answer type
+fn comments<RS: Read + Seek, W: Reader<RS>>(
+    workbook: &mut W,
+    layout: &Layout,
+    report: &mut ImportReport,
+) -> Result<HashMap<CID, Entry>, ImportError>
+where
+    ImportError: From<W::Error>,
+{
     let mut cids = HashMap::new();
 
-    for (sheet, domain) in sheets {
+    for (sheet, domain) in layout.sheets {
         let range = workbook.worksheet_range(sheet)?;
 
-        let mut comments: HashMap<CID, String> = range
+        let mut comments: HashMap<CID, String> = HashMap::new();
+        for row in range
             .rows()
-            .skip_while(|row| row[1] != "Comments and/or Remarks")
+            .skip_while(|row| *cell(row, layout.id_column) != layout.comments_marker)
             .skip(1)
-            .filter(|row| row[11].is_string() && row[13].is_string())
-            .map(|row| {
-                (
-                    format!("{}.{}", domain, row[11]).parse().unwrap(),
-                    row[13].to_string(),
-                )
+            .filter(|row| {
+                cell(row, layout.comment_id_column).is_string()
+                    && cell(row, layout.comment_column).is_string()
             })
-            .collect();
+        {
+            let id = format!("{}.{}", domain, cell(row, layout.comment_id_column));
+            match id.parse() {
+                Ok(cid) => {
+                    comments.insert(cid, cell(row, layout.comment_column).to_string());
+                }
+                Err(_) => report.push(ImportWarning::InvalidCid {
+                    sheet: (*sheet).to_owned(),
+                    id,
+                }),
+            }
+        }
 
-        let controls = range
+        for row in range
             .rows()
-            .skip(9)
-            .take_while(|row| row[1] != "Comments and/or Remarks")
+            .skip(layout.header_rows)
+            .take_while(|row| *cell(row, layout.id_column) != layout.comments_marker)
             .filter(|row| {
-                row[1]
+                cell(row, layout.id_column)
                     .to_string()
                     .chars()
                     .next()
-                    .map(|char| char.is_ascii_digit())
-                    .unwrap_or(false)
+                    .is_some_and(|char| char.is_ascii_digit())
             })
-            .map(|row| {
-                let cid: CID = format!(
-                    "{}.{}",
-                    domain,
-                    row[1].to_string().split_whitespace().next().unwrap()
-                )
-                .parse()
-                .unwrap();
-
-                (cid, Control::new(Answer::Title, comments.remove(&cid)))
-            });
-        cids.extend(controls);
+        {
+            let id = format!(
+                "{}.{}",
+                domain,
+                cell(row, layout.id_column)
+                    .to_string()
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+            );
+            let Ok(cid) = id.parse::<CID>() else {
+                report.push(ImportWarning::InvalidCid {
+                    sheet: (*sheet).to_owned(),
+                    id,
+                });
+                continue;
+            };
+            cids.insert(
+                cid,
+                Entry {
+                    answer: None,
+                    comment: comments.remove(&cid),
+                },
+            );
+        }
     }
     Ok(cids)
 }
 
+/// Rows of the output sheet which contain a control, e.g. "B 1.2"
+pub(crate) fn is_output_control(row: &[Data], layout: &Layout) -> bool {
+    is_output_id(row, layout) && !is_nist_mapping(row, layout)
+}
+
+pub(crate) fn is_output_id(row: &[Data], layout: &Layout) -> bool {
+    let Some(id) = cell(row, layout.output_id_column).as_string() else {
+        return false;
+    };
+    let mut chars = id.chars().skip(1);
+    chars.next().is_some_and(char::is_whitespace)
+        && chars.next().is_some_and(|char| char.is_ascii_digit())
+}
+
+/// Rows which are only part of the output for the NIST mapping
+pub(crate) fn is_nist_mapping(row: &[Data], layout: &Layout) -> bool {
+    cell(row, layout.nist_column).as_string() == Some("NIST MAPPING".to_owned())
+}
+
 fn extend_answer_from_output(
     output_ragne: &calamine::Range<Data>,
-    controls: &mut HashMap<CID, Control>,
+    layout: &Layout,
+    controls: &mut HashMap<CID, Entry>,
+    report: &mut ImportReport,
 ) {
-    let cids = output_ragne.rows().filter(|row| {
-        let Some(id) = row[0].as_string() else {
-            return false;
-        };
-        let mut chars = id.chars().skip(1);
-        chars.next().unwrap().is_whitespace()
-            && chars.next().unwrap().is_ascii_digit()
-            && row[13].as_string() != Some("NIST MAPPING".to_owned())
-    });
-
-    for row in cids {
-        let Some(control) = controls
-            .get_mut(&to_cid(&row[0].to_string()).expect(&format!("CID not found {}", row[0])))
-        else {
-            eprintln!("Output contains unlisted CID: {}", row[0].to_string());
+    for row in output_ragne
+        .rows()
+        .filter(|row| is_output_control(row, layout))
+    {
+        let id = cell(row, layout.output_id_column).to_string();
+        let Some(control) = to_cid(&id).ok().and_then(|cid| controls.get_mut(&cid)) else {
+            report.push(ImportWarning::UnlistedCid(id));
             continue;
         };
-        let answer = if ToCellDeserializer::is_empty(&row[3]) {
-            Answer::Title
+        let value = cell(row, layout.output_value_column);
+        control.answer = if ToCellDeserializer::is_empty(value) {
+            None
         } else {
-            Answer::Any(row[3].as_string().unwrap())
+            value.as_string().map(Answer::Any)
         };
-        control.set_answer(answer);
     }
 }
 
-fn extend_answer_from_form_controls<P: AsRef<Path>>(
-    controls: &mut HashMap<CID, Control>,
+fn extend_answer_from_form_controls(
+    controls: &mut HashMap<CID, Entry>,
     output_range: &calamine::Range<Data>,
-    path: P,
-) -> anyhow::Result<()> {
-    let mut zip = zip::ZipArchive::new(File::open(path)?)?;
+    form_controls: Vec<FormControl>,
+    entries: &HashMap<&str, Vec<String>>,
+    layout: &Layout,
+    report: &mut ImportReport,
+) {
+    for FormControl {
+        name,
+        output_row,
+        input,
+        linkage,
+    } in form_controls
+    {
+        let Some(output_row) = output_row else {
+            report.push(ImportWarning::MalformedFormControl(name));
+            continue;
+        };
+        let value_at = |column: usize| output_range.get_value((output_row, column as u32));
+
+        let id = value_at(layout.output_id_column)
+            .map(ToString::to_string)
+            .unwrap_or_default();
 
+        let value = value_at(layout.output_value_column);
+        let value = match linkage {
+            Linkage::Index => value.and_then(|value| value.as_i64()),
+            Linkage::Text => match value.and_then(|value| value.as_string()) {
+                Some(text) if !text.is_empty() => {
+                    let position = entries
+                        .get(input.as_str())
+                        .and_then(|entries| entries.iter().position(|entry| *entry == text));
+                    let Some(position) = position else {
+                        report.push(ImportWarning::UnknownListEntry {
+                            control: name,
+                            entry: text,
+                        });
+                        continue;
+                    };
+                    Some(position as i64 + 1)
+                }
+                _ => None,
+            },
+        };
+        let Some(value) = value else {
+            // Control maps to outdated control (this is probably wanted)
+            report.push(ImportWarning::OutdatedFormControl {
+                control: name,
+                id,
+                row: output_row + 1,
+            });
+            continue;
+        };
+
+        let Some((cid, entry)) = to_cid(&id)
+            .ok()
+            .and_then(|cid| Some((cid, controls.get_mut(&cid)?)))
+        else {
+            report.push(ImportWarning::UnlistedCid(id));
+            continue;
+        };
+
+        let control_type = value_at(layout.output_type_column)
+            .map(ToString::to_string)
+            .unwrap_or_default();
+
+        if !matches!(entry.answer, Some(Answer::Any(_))) {
+            report.push(ImportWarning::SkippedValue { cid, value });
+            continue;
+        }
+        match input_map(layout, cid, &input, value as usize, &control_type) {
+            Ok(answer) => entry.answer = Some(answer),
+            Err(warning) => report.push(warning),
+        }
+    }
+}
+
+/// Dropdown which is linked to a cell of the output sheet
+pub(crate) struct FormControl {
+    pub(crate) name: String,
+    /// Row of the linked cell (0-based), None if it does not link to the output sheet
+    pub(crate) output_row: Option<u32>,
+    /// Range of the selectable values, e.g. "_Input!$C$13:$C$17"
+    pub(crate) input: String,
+    pub(crate) linkage: Linkage,
+}
+
+/// What the linked cell of a form control holds
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Linkage {
+    /// Position of the selected entry (1-based), e.g. of xlsx dropdowns
+    Index,
+    /// Text of the selected entry, the default of ods list boxes
+    Text,
+}
+
+/// Entries of the input ranges of the layout, e.g. "_Input!$C$13:$C$17" -> ["No", ...].
+/// Ranges on a missing sheet are left out.
+pub(crate) fn input_entries<RS: Read + Seek, W: Reader<RS>>(
+    workbook: &mut W,
+    layout: &Layout,
+) -> HashMap<&'static str, Vec<String>> {
+    let mut sheets: HashMap<&str, Option<calamine::Range<Data>>> = HashMap::new();
+    layout
+        .inputs
+        .iter()
+        .filter_map(|(input, _kind)| {
+            let (sheet, cells) = input.split_once('!')?;
+            let (start, end) = cells.split_once(':')?;
+            let ((first, column), (last, _)) = (parse_reference(start)?, parse_reference(end)?);
+            let range = sheets
+                .entry(sheet)
+                .or_insert_with(|| workbook.worksheet_range(sheet).ok())
+                .as_ref()?;
+            let entries = (first..=last)
+                .map(|row| {
+                    range
+                        .get_value((row - 1, column))
+                        .map(ToString::to_string)
+                        .unwrap_or_default()
+                })
+                .collect();
+            Some((*input, entries))
+        })
+        .collect()
+}
+
+/// Form controls of an xlsx package, one ctrlProps part per control
+pub(crate) fn form_controls<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    layout: &Layout,
+) -> Result<Vec<FormControl>, ImportError> {
     let props = zip
         .file_names()
         .filter(|file| file.starts_with("xl/ctrlProps/"))
         .map(ToOwned::to_owned)
         .collect::<Vec<_>>();
 
-    for ctrl_prop in props {
-        let mut ctrl_prop = zip.by_name(&ctrl_prop)?;
+    let mut form_controls = Vec::new();
+    for name in props {
+        let mut ctrl_prop = zip.by_name(&name)?;
         let mut string = String::new();
-        string.try_reserve_exact(ctrl_prop.size() as usize)?;
         ctrl_prop.read_to_string(&mut string)?;
         let xml = Document::parse(string.as_str())?;
+        let root = xml.root_element();
 
-        let output_link = xml
-            .root()
-            .first_child()
-            .unwrap()
-            .attribute("fmlaLink")
-            .unwrap();
-
-        let input_link = xml
-            .root()
-            .first_child()
-            .unwrap()
-            .attribute("fmlaRange")
-            .unwrap();
-
-        let output_row = output_link
-            .strip_prefix("_Output!$D$")
-            .unwrap()
-            .parse::<u32>()?
-            - 1;
-
-        let id = output_range.get_value((output_row, 0)).unwrap();
-
-        let Some(value) = output_range.get_value((output_row, 3)).unwrap().as_i64() else {
-            eprintln!(
-                "Control maps to outdated control (this is probably wanted): {} row: {}",
-                id, output_row
-            );
-            continue;
-        };
-
-        let Some(entry) = controls.get_mut(&to_cid(&id.to_string()).unwrap()) else {
-            eprintln!("Output contains unlisted CID: {}", id);
+        let (Some(output_link), Some(input_link)) =
+            (root.attribute("fmlaLink"), root.attribute("fmlaRange"))
+        else {
+            // Checkboxes and buttons without a linked cell
             continue;
         };
 
-        let _type = output_range.get_value((output_row, 2)).unwrap().to_string();
+        let output_row = output_link
+            .strip_prefix(layout.output_link)
+            .and_then(|row| row.parse::<u32>().ok())
+            .and_then(|row| row.checked_sub(1));
+        form_controls.push(FormControl {
+            output_row,
+            input: input_link.to_owned(),
+            name,
+            linkage: Linkage::Index,
+        });
+    }
+    Ok(form_controls)
+}
 
-        if matches!(entry.answer(), Answer::Any(_)) {
-            entry.set_answer(input_map(input_link, value as usize, &_type));
-        } else {
-            eprintln!(
-                "Skipped {} with value of {}, existing: {:?}",
-                id, value, entry
-            )
-        }
+pub(crate) fn input_map(
+    layout: &Layout,
+    cid: CID,
+    input: &str,
+    value: usize,
+    control_type: &str,
+) -> Result<Answer, ImportWarning> {
+    let Some(kind) = layout.input_kind(input) else {
+        return Err(ImportWarning::UnknownInputRange {
+            cid,
+            range: input.to_owned(),
+        });
+    };
+    if let Some(expected) = kind.control_type()
+        && control_type != expected
+    {
+        return Err(ImportWarning::TypeMismatch {
+            cid,
+            expected: expected.to_owned(),
+            found: control_type.to_owned(),
+        });
     }
-    Ok(())
+    Ok(kind.answer(value))
 }
 
-fn input_map(input: &str, value: usize, _type: &str) -> Answer {
-    match input {
-        "_Input!$C$13:$C$18" => {
-            assert_eq!(_type, "C");
-            Answer::DetailedOptional(DetailedOptional::from_repr(value).unwrap_or_default())
-        }
-        "_Input!$C$13:$C$17" => {
-            assert_eq!(_type, "M");
-            Answer::Detailed(Detailed::from_repr(value).unwrap_or_default())
-        }
-        "_Input!$C$3:$C$4" => Answer::Bool(value > 1),
-        "_Input!$C$39:$C$43" => {
-            assert_eq!(_type, "M");
-            Answer::Occurence(Occurence::from_repr(value).unwrap_or_default())
-        }
-        "_Input!$C$45:$C$49" => {
-            assert_eq!(_type, "M");
-            Answer::Satisfaction(Satisfaction::from_repr(value).unwrap_or_default())
-        }
-        _ => unreachable!(),
+/// Inverse of `input_map`: the input range of the form control and the selected index
+pub(crate) fn output_map(layout: &Layout, answer: &Answer) -> Option<(&'static str, usize)> {
+    if answer.is_not_applicable() {
+        return None;
     }
+    let value = match answer {
+        Answer::DetailedOptional(detailed_optional) => *detailed_optional as usize,
+        Answer::Detailed(detailed) => *detailed as usize,
+        Answer::Bool(bool) => {
+            if *bool {
+                2
+            } else {
+                1
+            }
+        }
+        Answer::Occurence(occurence) => *occurence as usize,
+        Answer::Satisfaction(satisfaction) => *satisfaction as usize,
+        Answer::Any(_) => return None,
+    };
+    Some((layout.input_range(InputKind::of(answer)?)?, value))
 }
 
-fn to_cid(str: &str) -> anyhow::Result<CID> {
+pub(crate) fn to_cid(str: &str) -> Result<CID, CmmError> {
     let id = str
         .replace("P ", "People.")
         .replace("M ", "Process.")
@@ -292,5 +621,41 @@ fn to_cid(str: &str) -> anyhow::Result<CID> {
         .replace(" - Scope", "")
         .replace(" - Security incident Management", "")
         .replace(" - Security Analysis", "");
-    Ok(id.parse()?)
+    id.parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use cmm_core::answer::Detailed;
+
+    use super::*;
+
+    #[test]
+    fn test_input_map() {
+        let layout = WorkbookVersion::default().layout().unwrap();
+        let cid: CID = "Business.1.1".parse().unwrap();
+        assert_eq!(
+            input_map(layout, cid, "_Input!$C$13:$C$17", 5, "M"),
+            Ok(Answer::Detailed(Detailed::Fully))
+        );
+        assert_eq!(
+            input_map(layout, cid, "_Input!$C$13:$C$17", 5, "C"),
+            Err(ImportWarning::TypeMismatch {
+                cid,
+                expected: String::from("M"),
+                found: String::from("C")
+            })
+        );
+        assert_eq!(
+            input_map(layout, cid, "_Input!$C$1:$C$2", 1, "M"),
+            Err(ImportWarning::UnknownInputRange {
+                cid,
+                range: String::from("_Input!$C$1:$C$2")
+            })
+        );
+        assert_eq!(
+            output_map(layout, &Answer::Detailed(Detailed::Fully)),
+            Some(("_Input!$C$13:$C$17", 5))
+        );
+    }
 }
@@ -1,13 +1,737 @@
-use std::env::args;
+use std::{
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
-use cmm_compat::from_xlsx;
+use anyhow::{Context, bail};
+use clap::{Parser, Subcommand, ValueEnum};
+use cmm_compat::{from_ods, from_xlsx, schema_from_xlsx, to_ods, to_xlsx, verify_ods, verify_xlsx};
+use cmm_core::{
+    cid::Domain,
+    data::SOCData,
+    level::LevelThresholds,
+    lint::Lint,
+    narrative::NarrativeSection,
+    report::{ScoreEntry, ScoreReport},
+    schema::{Dependencies, Recommendations, Schema},
+    score::{Score, Stats},
+    scoring::{ScoringModelKind, WeightedDomains},
+};
+use serde_json::json;
+use strum::VariantArray;
 
-fn main() -> anyhow::Result<()> {
-    let soc_cmm = args().nth(1).expect("You need to provide a path");
+/// Work with SOC-CMM assessments from the terminal.
+///
+/// Assessments are read from filled workbooks (xlsx, ods) or from exports of the
+/// web edition (toml, json), the format is taken from the file extension.
+///
+/// Exit codes: 0 on success, 1 if validate, diff or verify found problems,
+/// 2 on invalid arguments or if the command failed.
+#[derive(Parser)]
+#[command(name = "cmm", version)]
+struct Cli {
+    /// Print JSON instead of human readable output
+    #[arg(long, global = true)]
+    json: bool,
 
-    let soc_data = from_xlsx(soc_cmm)?;
+    /// Schema of the assessed SOC-CMM release, the 2.3.4 schema is built in
+    #[arg(long, global = true, value_name = "JSON")]
+    schema: Option<PathBuf>,
 
-    println!("{}", toml::to_string_pretty(&soc_data).unwrap());
+    /// Opt-in dependencies between controls, e.g. dependencies-2.3.4.json.
+    /// Changes the scores, the workbook does not know them.
+    #[arg(long, global = true, value_name = "JSON")]
+    dependencies: Option<PathBuf>,
 
+    /// Recommendations per control for the report, e.g. recommendations-2.3.4.json
+    #[arg(long, global = true, value_name = "JSON")]
+    recommendations: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Import a filled workbook and print the assessment as TOML
+    Import { workbook: PathBuf },
+    /// Write an assessment into a copy of an empty workbook (xlsx or ods)
+    Export {
+        template: PathBuf,
+        assessment: PathBuf,
+        output: PathBuf,
+    },
+    /// Print the overall, domain and aspect scores with their levels
+    Score {
+        assessment: PathBuf,
+        /// SocCmm, WeakestLink or WeightedDomains
+        #[arg(long)]
+        model: Option<ScoringModelKind>,
+        /// Weight of a domain for WeightedDomains, e.g. Business=2, all domains default to 1
+        #[arg(long = "weight", value_name = "DOMAIN=WEIGHT", value_parser = domain_weight)]
+        weights: Vec<(Domain, f64)>,
+        /// Output format, json if --json is set and a table otherwise
+        #[arg(long, value_enum)]
+        format: Option<ScoreFormat>,
+    },
+    /// Check the answers against the schema, the lint rules and the evidence policy, and
+    /// that not applicable answers are justified
+    Validate {
+        assessment: PathBuf,
+        /// Lint rules of the assessed release, the 2.3.4 rules are built in and only
+        /// checked with the built-in schema
+        #[arg(long, value_name = "JSON")]
+        lint: Option<PathBuf>,
+    },
+    /// List the controls which differ between two assessments
+    Diff { before: PathBuf, after: PathBuf },
+    /// Convert an assessment between toml, json, xlsx and ods
+    Convert {
+        input: PathBuf,
+        output: PathBuf,
+        /// Empty workbook, needed to convert into xlsx or ods
+        #[arg(long)]
+        template: Option<PathBuf>,
+    },
+    /// Print a markdown report with profile, scores, narratives and recommendations
+    Report { assessment: PathBuf },
+    /// Compare the scores calculated by a filled workbook with the scores of cmm-core
+    Verify { workbook: PathBuf },
+    /// Extract the schema from an empty workbook, the previous schema fills the gaps
+    Schema {
+        workbook: PathBuf,
+        previous: Option<PathBuf>,
+    },
+}
+
+/// Validate, diff and verify found problems
+const FAILED: u8 = 1;
+/// Invalid arguments or the command failed, clap uses the same code for usage errors
+const ERROR: u8 = 2;
+
+/// Scores of the workbook may differ by rounding only, in percentage points
+const TOLERANCE: f64 = 0.01;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, ValueEnum)]
+enum ScoreFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Format {
+    Toml,
+    Json,
+    Xlsx,
+    Ods,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(FAILED),
+        Err(error) => {
+            eprintln!("error: {error:#}");
+            ExitCode::from(ERROR)
+        }
+    }
+}
+
+/// Runs the command, false if it found problems
+fn run(cli: Cli) -> anyhow::Result<bool> {
+    let schema: Schema = match &cli.schema {
+        Some(path) => serde_json::from_str(&read_to_string(path)?)
+            .with_context(|| format!("Invalid schema {}", path.display()))?,
+        None => serde_json::from_str(include_str!("../../scheme-2.3.4.json"))?,
+    };
+    let schema = match &cli.dependencies {
+        Some(path) => {
+            let dependencies: Dependencies = serde_json::from_str(&read_to_string(path)?)
+                .with_context(|| format!("Invalid dependencies {}", path.display()))?;
+            schema.with_dependencies(&dependencies)
+        }
+        None => schema,
+    };
+    let schema = match &cli.recommendations {
+        Some(path) => {
+            let recommendations: Recommendations = serde_json::from_str(&read_to_string(path)?)
+                .with_context(|| format!("Invalid recommendations {}", path.display()))?;
+            schema.with_recommendations(&recommendations)
+        }
+        None => schema,
+    };
+
+    match cli.command {
+        Command::Import { workbook } => {
+            let soc_data = load(&workbook, &schema)?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&soc_data)?);
+            } else {
+                print!("{}", toml::to_string_pretty(&soc_data)?);
+            }
+        }
+        Command::Export {
+            template,
+            assessment,
+            output,
+        } => save(&load(&assessment, &schema)?, &output, Some(&template))?,
+        Command::Score {
+            assessment,
+            model,
+            weights,
+            format,
+        } => {
+            let mut domain_weights = WeightedDomains::default();
+            for (domain, weight) in weights {
+                domain_weights.set_weight(domain, weight);
+            }
+            let stats = Stats::with_model(
+                load(&assessment, &schema)?,
+                schema,
+                model.unwrap_or_default().model(&domain_weights),
+            );
+            let format = match (format, cli.json) {
+                (Some(format), _) => format,
+                (None, true) => ScoreFormat::Json,
+                (None, false) => ScoreFormat::Table,
+            };
+            score(
+                &ScoreReport::new(&stats, &LevelThresholds::default()),
+                format,
+            )?;
+        }
+        Command::Validate { assessment, lint } => {
+            let lint: Option<Lint> = match (lint, &cli.schema) {
+                (Some(path), _) => Some(
+                    serde_json::from_str(&read_to_string(&path)?)
+                        .with_context(|| format!("Invalid lint rules {}", path.display()))?,
+                ),
+                (None, None) => Some(serde_json::from_str(include_str!("../../lint-2.3.4.json"))?),
+                // The rules refer to the controls of 2.3.4
+                (None, Some(_)) => None,
+            };
+            return validate(
+                &load(&assessment, &schema)?,
+                &schema,
+                lint.as_ref(),
+                cli.json,
+            );
+        }
+        Command::Diff { before, after } => {
+            return diff(&load(&before, &schema)?, &load(&after, &schema)?, cli.json);
+        }
+        Command::Convert {
+            input,
+            output,
+            template,
+        } => save(&load(&input, &schema)?, &output, template.as_deref())?,
+        Command::Report { assessment } => {
+            let soc_data = load(&assessment, &schema)?;
+            report(&Stats::new(soc_data, schema.clone()), &schema, cli.json)?;
+        }
+        Command::Verify { workbook } => return verify(&workbook, &schema, cli.json),
+        Command::Schema { workbook, previous } => {
+            let previous: Option<Schema> = match previous {
+                Some(path) => Some(serde_json::from_str(&read_to_string(path)?)?),
+                None => None,
+            };
+            let schema = schema_from_xlsx(workbook, previous.as_ref())?;
+            // Sorted keys keep the output diffable between releases
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::to_value(&schema)?)?
+            );
+        }
+    }
+    Ok(true)
+}
+
+/// Parses `--weight Business=2`
+fn domain_weight(arg: &str) -> Result<(Domain, f64), String> {
+    let (domain, weight) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected DOMAIN=WEIGHT, got {arg}"))?;
+    let domain = domain
+        .parse()
+        .map_err(|_| format!("unknown domain {domain}"))?;
+    let weight = weight
+        .parse()
+        .map_err(|_| format!("invalid weight {weight}"))?;
+    Ok((domain, weight))
+}
+
+fn format(path: &Path) -> anyhow::Result<Format> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    Ok(match extension.as_str() {
+        "toml" => Format::Toml,
+        "json" => Format::Json,
+        "xlsx" | "xlsm" => Format::Xlsx,
+        "ods" => Format::Ods,
+        _ => bail!(
+            "Unknown format of {}, use toml, json, xlsx or ods",
+            path.display()
+        ),
+    })
+}
+
+/// Reads an assessment, import anomalies of workbooks are printed to stderr
+/// so they do not end up in the output
+fn load(path: &Path, schema: &Schema) -> anyhow::Result<SOCData> {
+    let context = || format!("Cannot read {}", path.display());
+    let (soc_data, report) = match format(path)? {
+        Format::Toml => {
+            return toml::from_str(&read_to_string(path).with_context(context)?)
+                .with_context(context);
+        }
+        Format::Json => {
+            return serde_json::from_str(&read_to_string(path).with_context(context)?)
+                .with_context(context);
+        }
+        Format::Xlsx => from_xlsx(path, schema).with_context(context)?,
+        Format::Ods => from_ods(path, schema).with_context(context)?,
+    };
+    for warning in report.warnings() {
+        eprintln!("warning: {warning}");
+    }
+    Ok(soc_data)
+}
+
+/// Writes an assessment, workbooks are filled copies of `template`
+fn save(soc_data: &SOCData, path: &Path, template: Option<&Path>) -> anyhow::Result<()> {
+    let format = format(path)?;
+    match (format, template) {
+        (Format::Toml, _) => write(path, toml::to_string_pretty(soc_data)?)?,
+        (Format::Json, _) => write(path, serde_json::to_string_pretty(soc_data)?)?,
+        (Format::Xlsx, Some(template)) => to_xlsx(template, soc_data, path)?,
+        (Format::Ods, Some(template)) => to_ods(template, soc_data, path)?,
+        (Format::Xlsx | Format::Ods, None) => {
+            bail!("Writing a workbook needs an empty workbook as --template")
+        }
+    }
+    Ok(())
+}
+
+fn score(report: &ScoreReport, format: ScoreFormat) -> anyhow::Result<()> {
+    match format {
+        ScoreFormat::Json => println!("{}", serde_json::to_string_pretty(report)?),
+        ScoreFormat::Csv => {
+            println!("domain,aspect,title,type,score,max,percentage,level,label");
+            for (domain, aspect, title, kind, entry) in score_rows(report) {
+                let fields = [
+                    domain,
+                    aspect.map(|aspect| aspect.to_string()).unwrap_or_default(),
+                    title,
+                    kind.to_owned(),
+                    entry
+                        .score()
+                        .map(|score| score.to_string())
+                        .unwrap_or_default(),
+                    entry.max().to_string(),
+                    entry
+                        .percentage()
+                        .map(|percentage| percentage.to_string())
+                        .unwrap_or_default(),
+                    entry
+                        .level()
+                        .map(|level| level.to_string())
+                        .unwrap_or_default(),
+                    entry.label().cloned().unwrap_or_default(),
+                ];
+                println!("{}", fields.map(|field| csv_field(&field)).join(","));
+            }
+        }
+        ScoreFormat::Table => {
+            let cell = |entry: &ScoreEntry| {
+                format!(
+                    "{:>9} {:<22}",
+                    entry.score().map_or(String::from("N/A"), |score| format!(
+                        "{score:.2}/{}",
+                        entry.max()
+                    )),
+                    entry.label().map_or("", String::as_str)
+                )
+            };
+            // The labels are padded to align the columns, the last one is trimmed
+            let line = |name: &str, maturity: &ScoreEntry, capability: &ScoreEntry| {
+                let line = format!("{name:<40} {} {}", cell(maturity), cell(capability));
+                println!("{}", line.trim_end());
+            };
+            println!("{:<40} {:<32} Capability", "", "Maturity");
+            line("Overall", report.overall(), report.overall_capability());
+            for domain in report.domains() {
+                line(
+                    &domain.domain().to_string(),
+                    domain.maturity(),
+                    domain.capability(),
+                );
+                for aspect in domain.aspects() {
+                    line(
+                        &format!("  {} {}", aspect.id(), aspect.title()),
+                        aspect.maturity(),
+                        aspect.capability(),
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Domain, aspect, title, score type and score of every line of the csv output,
+/// the first lines are the overall maturity and capability without a domain
+fn score_rows(
+    report: &ScoreReport,
+) -> Vec<(String, Option<u8>, String, &'static str, &ScoreEntry)> {
+    let mut rows = Vec::from([
+        (
+            String::new(),
+            None,
+            String::from("Overall"),
+            "maturity",
+            report.overall(),
+        ),
+        (
+            String::new(),
+            None,
+            String::from("Overall"),
+            "capability",
+            report.overall_capability(),
+        ),
+    ]);
+    for domain in report.domains() {
+        let name = domain.domain().to_string();
+        rows.push((
+            name.clone(),
+            None,
+            name.clone(),
+            "maturity",
+            domain.maturity(),
+        ));
+        rows.push((
+            name.clone(),
+            None,
+            name.clone(),
+            "capability",
+            domain.capability(),
+        ));
+        for aspect in domain.aspects() {
+            for (kind, entry) in [
+                ("maturity", aspect.maturity()),
+                ("capability", aspect.capability()),
+            ] {
+                rows.push((
+                    name.clone(),
+                    Some(aspect.id()),
+                    aspect.title().clone(),
+                    kind,
+                    entry,
+                ));
+            }
+        }
+    }
+    rows
+}
+
+/// Quotes fields with separators, quotes or line breaks (RFC 4180)
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Score with two decimals and its maximum, e.g. "3.25/5"
+fn fraction(score: &Score) -> String {
+    match score.score() {
+        Some(value) => format!("{value:.2}/{}", score.max()),
+        None => String::from("N/A"),
+    }
+}
+
+fn validate(
+    soc_data: &SOCData,
+    schema: &Schema,
+    lint: Option<&Lint>,
+    json: bool,
+) -> anyhow::Result<bool> {
+    let mut mismatches = Vec::new();
+    for (cid, control) in Domain::VARIANTS
+        .iter()
+        .flat_map(|domain| soc_data.controls_by_domain(domain))
+    {
+        match schema.control_schema(cid) {
+            None => mismatches.push((cid, String::from("is not part of the schema"))),
+            Some(control_schema)
+                if !control
+                    .answer()
+                    .control_type_eq(control_schema.control_type()) =>
+            {
+                mismatches.push((
+                    cid,
+                    format!(
+                        "is answered as {} but the schema expects {:?}",
+                        control.answer(),
+                        control_schema.control_type()
+                    ),
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    let findings = lint.map(|lint| lint.check(soc_data)).unwrap_or_default();
+    let violations = soc_data.violations();
+    let justifications: Vec<_> = soc_data.missing_justifications().collect();
+
+    if json {
+        let mismatches: Vec<_> = mismatches
+            .iter()
+            .map(|(cid, message)| json!({ "cid": cid, "message": message }))
+            .collect();
+        let result = json!({
+            "schema": mismatches,
+            "lint": findings,
+            "policy": violations,
+            "justifications": justifications,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        for (cid, message) in &mismatches {
+            println!("{cid}: {message}");
+        }
+        for finding in &findings {
+            println!(
+                "{}: {} ({})",
+                finding.cid(),
+                finding.message(),
+                finding.related()
+            );
+        }
+        for violation in &violations {
+            println!("{}: {}", violation.cid(), violation.message());
+        }
+        for cid in &justifications {
+            println!("{cid}: is answered not applicable without a justification");
+        }
+    }
+    Ok(mismatches.is_empty()
+        && findings.is_empty()
+        && violations.is_empty()
+        && justifications.is_empty())
+}
+
+fn diff(before: &SOCData, after: &SOCData, json: bool) -> anyhow::Result<bool> {
+    let changes = before.diff(after);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&changes)?);
+    } else {
+        let value = |value: Option<&String>| value.map_or("-", String::as_str).replace('\n', " ");
+        for change in &changes {
+            println!(
+                "{} {}: {} -> {}",
+                change.cid(),
+                change.field(),
+                value(change.before()),
+                value(change.after())
+            );
+        }
+    }
+    Ok(changes.is_empty())
+}
+
+fn report(stats: &Stats, schema: &Schema, json: bool) -> anyhow::Result<()> {
+    let soc_data = stats.data();
+    let recommendations = stats.recommendations();
+    let profile: Vec<(&String, &String)> = schema
+        .profile()
+        .iter()
+        .filter_map(|(key, question)| {
+            Some((question.question(), soc_data.profile_answer(key.clone())?))
+        })
+        .collect();
+
+    if json {
+        let domains: Vec<_> = Domain::VARIANTS
+            .iter()
+            .map(|domain| {
+                json!({
+                    "domain": domain,
+                    "maturity": stats.maturity_by_domain(domain),
+                    "capability": stats.capability_by_domain(domain),
+                    "narrative": soc_data.domain_narrative(domain),
+                })
+            })
+            .collect();
+        let profile: serde_json::Map<String, serde_json::Value> = profile
+            .iter()
+            .map(|(question, answer)| (question.to_string(), json!(answer)))
+            .collect();
+        let report = json!({
+            "profile": profile,
+            "overall": stats.score_overall(),
+            "domains": domains,
+            "recommendations": recommendations,
+            "notes": soc_data.notes(),
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("# SOC-CMM assessment report\n");
+    for (question, answer) in &profile {
+        println!("- {question}: {answer}");
+    }
+    if !profile.is_empty() {
+        println!();
+    }
+    println!("Overall score: {}\n", fraction(&stats.score_overall()));
+    println!("| Domain | Maturity | Capability |");
+    println!("| --- | --- | --- |");
+    for domain in Domain::VARIANTS {
+        println!(
+            "| {domain} | {} | {} |",
+            fraction(&stats.maturity_by_domain(domain)),
+            fraction(&stats.capability_by_domain(domain))
+        );
+    }
+    for domain in Domain::VARIANTS {
+        let Some(narrative) = soc_data.domain_narrative(domain) else {
+            continue;
+        };
+        println!("\n## {domain}");
+        for section in NarrativeSection::VARIANTS {
+            if let Some(markdown) = narrative.get(*section) {
+                println!("\n### {section}\n\n{}", markdown.trim());
+            }
+        }
+    }
+    if !recommendations.is_empty() {
+        println!("\n## Recommendations\n");
+        for recommendation in &recommendations {
+            println!(
+                "- {} (level {}): {}",
+                recommendation.cid(),
+                recommendation.level(),
+                recommendation.text()
+            );
+        }
+    }
+    if let Some(notes) = soc_data.notes() {
+        println!("\n## Notes\n\n{}", notes.trim());
+    }
     Ok(())
 }
+
+fn verify(workbook: &Path, schema: &Schema, json: bool) -> anyhow::Result<bool> {
+    let (parity, report) = match format(workbook)? {
+        Format::Xlsx => verify_xlsx(workbook, schema)?,
+        Format::Ods => verify_ods(workbook, schema)?,
+        Format::Toml | Format::Json => bail!("Only workbooks contain calculated scores"),
+    };
+    for warning in report.warnings() {
+        eprintln!("warning: {warning}");
+    }
+    let mismatches = parity
+        .iter()
+        .filter(|score| !score.matches(TOLERANCE))
+        .count();
+
+    if json {
+        let scores: Vec<_> = parity
+            .iter()
+            .map(|score| {
+                json!({
+                    "domain": score.domain(),
+                    "aspect": score.aspect(),
+                    "kind": score.kind().to_string(),
+                    "workbook": score.workbook(),
+                    "core": score.core(),
+                    "delta": score.delta(),
+                    "matches": score.matches(TOLERANCE),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&scores)?);
+        return Ok(mismatches == 0);
+    }
+
+    let percentage =
+        |score: Option<f64>| score.map_or(String::from("N/A"), |score| format!("{score:.2}"));
+    println!(
+        "{:<25} {:>9} {:>9} {:>7}",
+        "Score", "Workbook", "cmm-core", "Delta"
+    );
+    for score in &parity {
+        let name = match score.aspect() {
+            Some(aspect) => format!("{} {aspect} {}", score.domain(), score.kind()),
+            None => format!("{} {}", score.domain(), score.kind()),
+        };
+        println!(
+            "{name:<25} {:>9} {:>9} {:>7}{}",
+            percentage(score.workbook()),
+            percentage(score.core()),
+            score
+                .delta()
+                .map_or(String::from("-"), |delta| format!("{delta:+.2}")),
+            if score.matches(TOLERANCE) { "" } else { "  !" }
+        );
+    }
+    println!("{mismatches} of {} scores differ", parity.len());
+    Ok(mismatches == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("Business Drivers"), "Business Drivers");
+        assert_eq!(csv_field("Roles, Hierarchy"), "\"Roles, Hierarchy\"");
+        assert_eq!(csv_field("\"SOC\" charter"), "\"\"\"SOC\"\" charter\"");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+    }
+
+    #[test]
+    fn test_score_rows() {
+        let schema: Schema = serde_json::from_str(include_str!("../../scheme-2.3.4.json")).unwrap();
+        let stats = Stats::new(SOCData::new(IndexMap::new(), None, IndexMap::new()), schema);
+        let report = ScoreReport::new(&stats, &LevelThresholds::default());
+        let rows = score_rows(&report);
+
+        let (domain, aspect, title, kind, overall) = &rows[0];
+        assert_eq!(
+            (domain.as_str(), aspect, title.as_str(), *kind),
+            ("", &None, "Overall", "maturity")
+        );
+        assert_eq!(overall.max(), 5.0);
+        assert_eq!(overall.score(), None);
+        let (domain, _, title, kind, overall) = &rows[1];
+        assert_eq!(
+            (domain.as_str(), title.as_str(), *kind),
+            ("", "Overall", "capability")
+        );
+        assert_eq!(overall.max(), 3.0);
+
+        let domains: Vec<_> = rows
+            .iter()
+            .filter(|(domain, aspect, ..)| !domain.is_empty() && aspect.is_none())
+            .map(|(domain, _, _, kind, _)| (domain.as_str(), *kind))
+            .collect();
+        assert_eq!(
+            &domains[..2],
+            [("Business", "maturity"), ("Business", "capability")]
+        );
+        assert_eq!(domains.len(), 2 * Domain::VARIANTS.len());
+        assert_eq!(rows[4].1, Some(1));
+    }
+}
@@ -0,0 +1,219 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{Cursor, Read, Seek, Write},
+    path::Path,
+};
+
+use calamine::{Ods, Reader};
+use cmm_core::{data::SOCData, schema::Schema};
+use roxmltree::Document;
+use zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    ExportError, FormControl, ImportError, ImportReport, Linkage, WorkbookVersion,
+    export::{comment_cells, free_text_cells, output_rows, read_part, selection},
+    import, input_entries,
+    layout::Layout,
+    sheet::{CellPosition, CellValue, cell_reference, parse_reference},
+    table::set_table_cells,
+};
+
+const FORM: &str = "urn:oasis:names:tc:opendocument:xmlns:form:1.0";
+
+/// Imports an assessment which was saved as OpenDocument spreadsheet, e.g. by LibreOffice
+pub fn from_ods<P: AsRef<Path>>(
+    path: P,
+    schema: &Schema,
+) -> Result<(SOCData, ImportReport), ImportError> {
+    from_ods_reader(File::open(path)?, schema)
+}
+
+pub fn from_ods_reader<R: Read + Seek>(
+    mut reader: R,
+    schema: &Schema,
+) -> Result<(SOCData, ImportReport), ImportError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let mut zip = ZipArchive::new(Cursor::new(bytes.as_slice()))?;
+    let version = WorkbookVersion::detect(&mut zip);
+    let workbook: Ods<_> = Ods::new(Cursor::new(bytes.as_slice()))?;
+    import(
+        workbook,
+        version,
+        |layout| form_controls(&mut zip, layout),
+        schema,
+    )
+}
+
+/// Writes the answers and comments of `data` into a copy of an empty workbook saved as ods.
+/// LibreOffice only recalculates the scores on loading if it is configured to do so,
+/// otherwise use Data > Calculate > Recalculate Hard.
+pub fn to_ods<P: AsRef<Path>, Q: AsRef<Path>>(
+    template: P,
+    data: &SOCData,
+    output: Q,
+) -> Result<(), ExportError> {
+    let template = std::fs::read(template)?;
+    to_ods_writer(Cursor::new(template), data, File::create(output)?)
+}
+
+pub fn to_ods_writer<R: Read + Seek, W: Write + Seek>(
+    mut template: R,
+    data: &SOCData,
+    output: W,
+) -> Result<(), ExportError> {
+    let mut bytes = Vec::new();
+    template.read_to_end(&mut bytes)?;
+
+    let mut workbook: Ods<_> = Ods::new(Cursor::new(bytes.as_slice()))?;
+    let mut zip = ZipArchive::new(Cursor::new(bytes.as_slice()))?;
+    let version = WorkbookVersion::detect(&mut zip).unwrap_or_default();
+    let layout = version
+        .layout()
+        .ok_or(ExportError::UnsupportedVersion(version))?;
+
+    let output_range = workbook.worksheet_range(layout.output_sheet)?;
+    let output_rows = output_rows(&output_range, layout);
+    let entries = input_entries(&mut workbook, layout);
+
+    // The list boxes show the value of their linked cell
+    let mut output_cells: BTreeMap<CellPosition, CellValue> = BTreeMap::new();
+    for form_control in form_controls(&mut zip, layout)? {
+        let Some(row) = form_control.output_row.map(|row| row + 1) else {
+            continue;
+        };
+        let Some(value) = selection(layout, &output_rows, data, row, &form_control.input) else {
+            continue;
+        };
+        let value = match form_control.linkage {
+            Linkage::Index => CellValue::Number(value as f64),
+            Linkage::Text => CellValue::Text(
+                entries
+                    .get(form_control.input.as_str())
+                    .and_then(|entries| entries.get(value - 1))
+                    .cloned()
+                    .ok_or_else(|| {
+                        ExportError::Malformed(format!(
+                            "{} has no entry {value}",
+                            form_control.input
+                        ))
+                    })?,
+            ),
+        };
+        output_cells.insert((row, layout.output_value_column as u32), value);
+    }
+    output_cells.extend(free_text_cells(layout, &output_rows, data));
+
+    let mut content = read_part(&mut zip, "content.xml")?;
+    content = set_table_cells(&content, layout.output_sheet, &output_cells)?;
+    for (sheet, domain) in layout.sheets {
+        let range = workbook.worksheet_range(sheet)?;
+        let cells = comment_cells(layout, &range, domain, data);
+        if !cells.is_empty() {
+            content = set_table_cells(&content, sheet, &cells)?;
+        }
+    }
+
+    // The mimetype has to stay the first and uncompressed entry, raw copies keep both
+    let mut writer = ZipWriter::new(output);
+    for i in 0..zip.len() {
+        let file = zip.by_index_raw(i)?;
+        if file.name() == "content.xml" {
+            drop(file);
+            writer.start_file("content.xml", SimpleFileOptions::default())?;
+            writer.write_all(content.as_bytes())?;
+        } else {
+            writer.raw_copy_file(file)?;
+        }
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// List boxes of the spreadsheet, their cell addresses are converted to the xlsx notation
+/// of the layout, e.g. "$_Output.$D$12" -> "_Output!$D$12". Without a list linkage type
+/// the linked cell holds the text of the selected entry.
+pub(crate) fn form_controls<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    layout: &Layout,
+) -> Result<Vec<FormControl>, ImportError> {
+    let xml = read_part(zip, "content.xml")?;
+    let document = Document::parse(&xml)?;
+
+    Ok(document
+        .descendants()
+        .filter(|node| {
+            node.tag_name().namespace() == Some(FORM)
+                && matches!(node.tag_name().name(), "listbox" | "combobox")
+        })
+        .filter_map(|node| {
+            let output_link = to_excel_reference(node.attribute((FORM, "linked-cell"))?)?;
+            let input = to_excel_reference(node.attribute((FORM, "source-cell-range"))?)?;
+            let output_row = output_link
+                .strip_prefix(layout.output_link)
+                .and_then(|row| row.parse::<u32>().ok())
+                .and_then(|row| row.checked_sub(1));
+            let name = node
+                .attribute((FORM, "name"))
+                .or(node.attribute((FORM, "id")))
+                .unwrap_or_default()
+                .to_owned();
+            // ODF names the index linkage "selection-indexes", LibreOffice "selection-index"
+            let linkage = match node.attribute((FORM, "list-linkage-type")) {
+                Some(linkage) if linkage.starts_with("selection-index") => Linkage::Index,
+                _ => Linkage::Text,
+            };
+            Some(FormControl {
+                name,
+                output_row,
+                input,
+                linkage,
+            })
+        })
+        .collect())
+}
+
+/// "$'_Input'.$C$13:.$C$17" -> "_Input!$C$13:$C$17"
+fn to_excel_reference(reference: &str) -> Option<String> {
+    let mut sheet = None;
+    let mut cells = Vec::new();
+    for part in reference.split(':') {
+        let (table, cell) = part.rsplit_once('.').unwrap_or(("", part));
+        let table = table.trim_start_matches('$').trim_matches('\'');
+        if sheet.is_none() && !table.is_empty() {
+            sheet = Some(table);
+        }
+        let reference = cell_reference(parse_reference(cell)?);
+        let row_start = reference.find(|char: char| char.is_ascii_digit())?;
+        cells.push(format!(
+            "${}${}",
+            &reference[..row_start],
+            &reference[row_start..]
+        ));
+    }
+    Some(format!("{}!{}", sheet?, cells.join(":")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_excel_reference() {
+        assert_eq!(
+            to_excel_reference("$_Output.$D$12").as_deref(),
+            Some("_Output!$D$12")
+        );
+        assert_eq!(
+            to_excel_reference("_Input.C13:_Input.C17").as_deref(),
+            Some("_Input!$C$13:$C$17")
+        );
+        assert_eq!(
+            to_excel_reference("$'_Input'.$C$45:.$C$49").as_deref(),
+            Some("_Input!$C$45:$C$49")
+        );
+        assert_eq!(to_excel_reference("C13"), None);
+    }
+}
@@ -0,0 +1,146 @@
+use calamine::{Data, DataType, Range};
+use cmm_core::{profile::QuestionType, schema::Schema};
+
+use crate::{ImportReport, ImportWarning};
+
+/// Answers of the profile sheet, keyed like the profile questions of the schema.
+/// A question is found by its text, the answer is the next filled cell of the same row.
+pub(crate) fn profile(
+    range: &Range<Data>,
+    schema: &Schema,
+    report: &mut ImportReport,
+) -> Vec<(String, String)> {
+    let mut answers = Vec::new();
+    for (key, question) in schema.profile() {
+        let Some(value) = range.rows().find_map(|row| {
+            let position = row.iter().position(|cell| {
+                cell.as_string()
+                    .is_some_and(|text| same(&text, question.question()))
+            })?;
+            row.iter().skip(position + 1).find(|cell| !cell.is_empty())
+        }) else {
+            continue;
+        };
+        match normalize(question.question_type(), value) {
+            Some(answer) => answers.push((key.clone(), answer)),
+            None => report.push(ImportWarning::InvalidProfileValue {
+                key: key.clone(),
+                value: value.to_string(),
+            }),
+        }
+    }
+    answers
+}
+
+/// Compares texts ignoring case and whitespace
+pub(crate) fn same(text: &str, question: &str) -> bool {
+    let words = |text: &str| {
+        text.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    };
+    words(text) == words(question)
+}
+
+/// The value in the format of the web edition, None if it is not valid for the question
+fn normalize(question_type: &QuestionType, value: &Data) -> Option<String> {
+    let text = value.to_string().trim().to_owned();
+    match question_type {
+        QuestionType::Text => Some(text),
+        QuestionType::YesNo => match text.to_lowercase().as_str() {
+            "yes" | "true" => Some(String::from("Yes")),
+            "no" | "false" => Some(String::from("No")),
+            _ => None,
+        },
+        QuestionType::Number => {
+            let number = value.as_f64().or_else(|| text.parse().ok())?;
+            Some(if number.fract() == 0.0 {
+                format!("{}", number as i64)
+            } else {
+                number.to_string()
+            })
+        }
+        QuestionType::Date => match value {
+            Data::DateTime(date) => Some(from_serial(date.as_f64())),
+            Data::Float(serial) => Some(from_serial(*serial)),
+            Data::Int(serial) => Some(from_serial(*serial as f64)),
+            _ => {
+                let date = text.get(..10)?;
+                let valid = date.char_indices().all(|(i, char)| match i {
+                    4 | 7 => char == '-',
+                    _ => char.is_ascii_digit(),
+                });
+                valid.then(|| date.to_owned())
+            }
+        },
+        QuestionType::Select { items } => items.iter().find(|item| same(item, &text)).cloned(),
+    }
+}
+
+/// yyyy-mm-dd of an Excel serial date (days since 1899-12-30)
+fn from_serial(serial: f64) -> String {
+    // Days since 1970-01-01, converted with the civil from days algorithm of Howard Hinnant
+    let days = serial.floor() as i64 - 25569;
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_serial() {
+        assert_eq!(from_serial(25569.0), "1970-01-01");
+        assert_eq!(from_serial(45292.0), "2024-01-01");
+        assert_eq!(from_serial(45351.75), "2024-02-29");
+    }
+
+    #[test]
+    fn test_normalize() {
+        let select = QuestionType::Select {
+            items: vec![String::from("1-5"), String::from("Health Care")],
+        };
+        assert_eq!(
+            normalize(&select, &Data::String(String::from(" health  care"))),
+            Some(String::from("Health Care"))
+        );
+        assert_eq!(
+            normalize(&select, &Data::String(String::from("6-10"))),
+            None
+        );
+        assert_eq!(
+            normalize(&QuestionType::YesNo, &Data::String(String::from("yes"))),
+            Some(String::from("Yes"))
+        );
+        assert_eq!(
+            normalize(&QuestionType::Number, &Data::Float(7.0)),
+            Some(String::from("7"))
+        );
+        assert_eq!(
+            normalize(
+                &QuestionType::Date,
+                &Data::String(String::from("2024-03-01"))
+            ),
+            Some(String::from("2024-03-01"))
+        );
+        assert_eq!(
+            normalize(&QuestionType::Date, &Data::String(String::from("March"))),
+            None
+        );
+    }
+}
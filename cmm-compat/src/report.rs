@@ -0,0 +1,73 @@
+use cmm_core::cid::CID;
+use thiserror::Error;
+
+use crate::WorkbookVersion;
+
+/// Anomalies of an import which did not stop it, the affected values were skipped
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum ImportWarning {
+    #[error("{sheet} contains an invalid control id: {id}")]
+    InvalidCid { sheet: String, id: String },
+    #[error("Output contains unlisted CID: {0}")]
+    UnlistedCid(String),
+    #[error("Form control {0} has no valid link to the output sheet")]
+    MalformedFormControl(String),
+    #[error("Form control {control} maps to outdated control {id} in row {row}")]
+    OutdatedFormControl {
+        control: String,
+        id: String,
+        row: u32,
+    },
+    #[error("Form control {control} is set to {entry}, which is not one of its entries")]
+    UnknownListEntry { control: String, entry: String },
+    #[error("Form control of {cid} uses the unknown input range {range}")]
+    UnknownInputRange { cid: CID, range: String },
+    #[error("Skipped {cid} with value of {value}, it has no form control answer type")]
+    SkippedValue { cid: CID, value: i64 },
+    #[error("{cid} is expected to be of type {expected} but the workbook marks it as {found}")]
+    TypeMismatch {
+        cid: CID,
+        expected: String,
+        found: String,
+    },
+    #[error("{0} is missing in the workbook")]
+    MissingControl(CID),
+    #[error(
+        "SOC-CMM version of the workbook could not be detected, assuming {}",
+        WorkbookVersion::default()
+    )]
+    UnknownVersion,
+    #[error("Workbook has no {0} sheet, the profile was not imported")]
+    MissingProfile(String),
+    #[error("{value} is not a valid answer for the profile question {key}")]
+    InvalidProfileValue { key: String, value: String },
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct ImportReport {
+    version: Option<WorkbookVersion>,
+    warnings: Vec<ImportWarning>,
+}
+
+impl ImportReport {
+    /// Release of the imported workbook
+    pub fn version(&self) -> Option<&WorkbookVersion> {
+        self.version.as_ref()
+    }
+
+    pub fn warnings(&self) -> &Vec<ImportWarning> {
+        &self.warnings
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    pub(crate) fn set_version(&mut self, version: WorkbookVersion) {
+        self.version = Some(version);
+    }
+
+    pub(crate) fn push(&mut self, warning: ImportWarning) {
+        self.warnings.push(warning);
+    }
+}
@@ -0,0 +1,389 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use roxmltree::{Document, Node};
+
+use crate::ExportError;
+
+/// Value written into a worksheet cell
+pub(crate) enum CellValue {
+    Number(f64),
+    Text(String),
+}
+
+/// Cell position, the row is 1-based like in Excel and the column 0-based (A = 0)
+pub(crate) type CellPosition = (u32, u32);
+
+/// Sets cell values in the xml of a worksheet and returns the new xml.
+/// The style of existing cells is kept, their formulas are replaced by the value.
+/// Cells which hold the formula of a shared formula are refused, the other cells of the
+/// shared formula would lose their formula.
+pub(crate) fn set_cells(
+    xml: &str,
+    cells: &BTreeMap<CellPosition, CellValue>,
+) -> Result<String, ExportError> {
+    let document = Document::parse(xml)?;
+    let sheet_data = document
+        .descendants()
+        .find(|node| node.has_tag_name("sheetData"))
+        .ok_or(ExportError::Malformed(String::from(
+            "Worksheet without sheetData",
+        )))?;
+    let rows: Vec<Node> = sheet_data
+        .children()
+        .filter(|node| node.has_tag_name("row"))
+        .collect();
+
+    let mut by_row: BTreeMap<u32, Vec<(u32, &CellValue)>> = BTreeMap::new();
+    for ((row, column), value) in cells {
+        by_row.entry(*row).or_default().push((*column, value));
+    }
+
+    let mut replacements: Vec<(Range<usize>, String)> = Vec::new();
+    let mut new_rows = String::new();
+    for (row_number, cells) in by_row {
+        if let Some(row) = rows
+            .iter()
+            .find(|row| row_number_of(row) == Some(row_number))
+        {
+            replacements.push((row.range(), rebuild_row(xml, row, &cells)?));
+        } else if let Some(next) = rows
+            .iter()
+            .find(|row| row_number_of(row).is_some_and(|number| number > row_number))
+        {
+            let start = next.range().start;
+            replacements.push((start..start, new_row(row_number, &cells)));
+        } else {
+            new_rows.push_str(&new_row(row_number, &cells));
+        }
+    }
+
+    if !new_rows.is_empty() {
+        let range = sheet_data.range();
+        if xml[range.clone()].ends_with("/>") {
+            replacements.push((range, format!("<sheetData>{new_rows}</sheetData>")));
+        } else {
+            let end = range.start
+                + xml[range]
+                    .rfind("</")
+                    .ok_or(ExportError::Malformed(String::from("Malformed sheetData")))?;
+            replacements.push((end..end, new_rows));
+        }
+    }
+
+    replacements.sort_by_key(|(range, _)| range.start);
+    let mut result = String::with_capacity(xml.len());
+    let mut cursor = 0;
+    for (range, text) in replacements {
+        result.push_str(&xml[cursor..range.start]);
+        result.push_str(&text);
+        cursor = range.end;
+    }
+    result.push_str(&xml[cursor..]);
+    Ok(result)
+}
+
+/// Sets or adds an attribute on the root element, e.g. `sel` of a form control
+pub(crate) fn set_root_attribute(
+    xml: &str,
+    name: &str,
+    value: &str,
+) -> Result<String, ExportError> {
+    let document = Document::parse(xml)?;
+    let start = document.root_element().range().start;
+    let tag_end = start
+        + xml[start..]
+            .find('>')
+            .ok_or(ExportError::Malformed(String::from(
+                "Malformed root element",
+            )))?;
+    let tag = &xml[start..tag_end];
+
+    let attribute = format!(" {name}=\"");
+    if let Some(position) = tag.find(&attribute) {
+        let value_start = start + position + attribute.len();
+        let value_end = value_start
+            + xml[value_start..]
+                .find('"')
+                .ok_or(ExportError::Malformed(format!(
+                    "Malformed attribute {name}"
+                )))?;
+        return Ok(format!(
+            "{}{}{}",
+            &xml[..value_start],
+            escape(value),
+            &xml[value_end..]
+        ));
+    }
+
+    let insert_at = if tag.ends_with('/') {
+        tag_end - 1
+    } else {
+        tag_end
+    };
+    Ok(format!(
+        "{} {name}=\"{}\"{}",
+        &xml[..insert_at],
+        escape(value),
+        &xml[insert_at..]
+    ))
+}
+
+/// Makes Excel recalculate the workbook when it is opened, the cached scores are outdated after
+/// an export. A missing `calcPr` is inserted where CT_Workbook expects it.
+pub(crate) fn full_calc_on_load(xml: &str) -> Result<String, ExportError> {
+    let document = Document::parse(xml)?;
+    if let Some(calc_pr) = document
+        .descendants()
+        .find(|node| node.has_tag_name("calcPr"))
+    {
+        let range = calc_pr.range();
+        let updated = set_root_attribute(&xml[range.clone()], "fullCalcOnLoad", "1")?;
+        return Ok(format!(
+            "{}{updated}{}",
+            &xml[..range.start],
+            &xml[range.end..]
+        ));
+    }
+
+    // calcPr follows the last of these elements of the workbook
+    let preceding = [
+        "sheets",
+        "functionGroups",
+        "externalReferences",
+        "definedNames",
+    ];
+    let end = document
+        .root_element()
+        .children()
+        .filter(|node| preceding.contains(&node.tag_name().name()))
+        .map(|node| node.range().end)
+        .max()
+        .ok_or(ExportError::Malformed(String::from(
+            "Workbook without sheets",
+        )))?;
+    Ok(format!(
+        "{}<calcPr fullCalcOnLoad=\"1\"/>{}",
+        &xml[..end],
+        &xml[end..]
+    ))
+}
+
+/// A1 style reference, e.g. (12, 3) -> D12
+pub(crate) fn cell_reference((row, column): CellPosition) -> String {
+    let mut letters = String::new();
+    let mut column = column + 1;
+    while column > 0 {
+        let remainder = (column - 1) % 26;
+        letters.insert(0, (b'A' + remainder as u8) as char);
+        column = (column - 1) / 26;
+    }
+    format!("{letters}{row}")
+}
+
+/// Position of an A1 style reference, e.g. $D$12 -> (12, 3)
+pub(crate) fn parse_reference(reference: &str) -> Option<CellPosition> {
+    let reference = reference.replace('$', "");
+    let column = column_of(&reference)?;
+    let row = reference
+        .trim_start_matches(|char: char| char.is_ascii_uppercase())
+        .parse()
+        .ok()
+        .filter(|row| *row > 0)?;
+    Some((row, column))
+}
+
+/// Column of an A1 style reference, e.g. D12 -> 3
+fn column_of(reference: &str) -> Option<u32> {
+    let letters: String = reference
+        .chars()
+        .take_while(|char| char.is_ascii_uppercase())
+        .collect();
+    if letters.is_empty() {
+        return None;
+    }
+    Some(
+        letters
+            .bytes()
+            .fold(0, |column, letter| column * 26 + (letter - b'A') as u32 + 1)
+            - 1,
+    )
+}
+
+fn row_number_of(row: &Node) -> Option<u32> {
+    row.attribute("r")?.parse().ok()
+}
+
+/// Merges the new cells into the existing ones, cells have to stay ordered by column
+fn rebuild_row(xml: &str, row: &Node, cells: &[(u32, &CellValue)]) -> Result<String, ExportError> {
+    let row_number = row_number_of(row).unwrap_or_default();
+    let range = row.range();
+
+    let start_tag = match row.first_child() {
+        Some(child) => xml[range.start..child.range().start].to_owned(),
+        None if xml[range.clone()].ends_with("/>") => {
+            format!("{}>", &xml[range.start..range.end - 2])
+        }
+        None => xml[range.clone()].trim_end_matches("</row>").to_owned(),
+    };
+    // spans is only an optimization hint and would be outdated by new cells
+    let start_tag = remove_attribute(&start_tag, "spans");
+
+    let mut columns: BTreeMap<u32, String> = row
+        .children()
+        .filter(|node| node.has_tag_name("c"))
+        .filter_map(|cell| {
+            let column = column_of(cell.attribute("r")?)?;
+            Some((column, xml[cell.range()].to_owned()))
+        })
+        .collect();
+    for (column, value) in cells {
+        let existing = row.children().find(|node| {
+            node.has_tag_name("c") && node.attribute("r").and_then(column_of) == Some(*column)
+        });
+        if existing.is_some_and(|cell| is_shared_formula(&cell)) {
+            return Err(ExportError::SharedFormula(cell_reference((
+                row_number, *column,
+            ))));
+        }
+        let style = existing.and_then(|cell| cell.attribute("s"));
+        columns.insert(*column, cell((row_number, *column), style, value));
+    }
+
+    Ok(format!(
+        "{start_tag}{}</row>",
+        columns.into_values().collect::<String>()
+    ))
+}
+
+/// The cell holds the formula of a shared formula, e.g. `<f t="shared" ref="D2:D9" si="0">`.
+/// The other cells only reference it by `si`.
+fn is_shared_formula(cell: &Node) -> bool {
+    cell.children().any(|node| {
+        node.has_tag_name("f")
+            && node.attribute("t") == Some("shared")
+            && node.attribute("ref").is_some()
+    })
+}
+
+fn new_row(row_number: u32, cells: &[(u32, &CellValue)]) -> String {
+    format!(
+        "<row r=\"{row_number}\">{}</row>",
+        cells
+            .iter()
+            .map(|(column, value)| cell((row_number, *column), None, value))
+            .collect::<String>()
+    )
+}
+
+fn cell(position: CellPosition, style: Option<&str>, value: &CellValue) -> String {
+    let reference = cell_reference(position);
+    let style = style
+        .map(|style| format!(" s=\"{style}\""))
+        .unwrap_or_default();
+    match value {
+        CellValue::Number(number) => format!("<c r=\"{reference}\"{style}><v>{number}</v></c>"),
+        CellValue::Text(text) => format!(
+            "<c r=\"{reference}\"{style} t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+            escape(text)
+        ),
+    }
+}
+
+pub(crate) fn remove_attribute(tag: &str, name: &str) -> String {
+    let attribute = format!(" {name}=\"");
+    let Some(start) = tag.find(&attribute) else {
+        return tag.to_owned();
+    };
+    let value_start = start + attribute.len();
+    match tag[value_start..].find('"') {
+        Some(end) => format!("{}{}", &tag[..start], &tag[value_start + end + 1..]),
+        None => tag.to_owned(),
+    }
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_reference() {
+        assert_eq!(cell_reference((12, 3)), "D12");
+        assert_eq!(cell_reference((1, 25)), "Z1");
+        assert_eq!(cell_reference((1, 27)), "AB1");
+        assert_eq!(column_of("AB1"), Some(27));
+        assert_eq!(column_of("N45"), Some(13));
+        assert_eq!(parse_reference("$D$12"), Some((12, 3)));
+        assert_eq!(parse_reference("12"), None);
+        assert_eq!(parse_reference("A0"), None);
+    }
+
+    #[test]
+    fn test_set_cells() {
+        let xml = r#"<worksheet><sheetData><row r="2" spans="1:4"><c r="A2" t="s"><v>0</v></c><c r="D2" s="5"><f>SUM(A1)</f><v>0</v></c></row><row r="5"/></sheetData></worksheet>"#;
+        let cells = BTreeMap::from([
+            ((2, 3), CellValue::Number(3.0)),
+            ((3, 13), CellValue::Text(String::from("a & b"))),
+            ((5, 1), CellValue::Number(1.0)),
+            ((7, 0), CellValue::Number(2.0)),
+        ]);
+        assert_eq!(
+            set_cells(xml, &cells).unwrap(),
+            r#"<worksheet><sheetData><row r="2"><c r="A2" t="s"><v>0</v></c><c r="D2" s="5"><v>3</v></c></row><row r="3"><c r="N3" t="inlineStr"><is><t xml:space="preserve">a &amp; b</t></is></c></row><row r="5"><c r="B5"><v>1</v></c></row><row r="7"><c r="A7"><v>2</v></c></row></sheetData></worksheet>"#
+        );
+    }
+
+    #[test]
+    fn test_shared_formula() {
+        let xml = r#"<worksheet><sheetData><row r="2"><c r="D2"><f t="shared" ref="D2:D3" si="0">A2*2</f><v>0</v></c></row><row r="3"><c r="D3"><f t="shared" si="0"/><v>0</v></c></row></sheetData></worksheet>"#;
+        let cells = BTreeMap::from([((2, 3), CellValue::Number(1.0))]);
+        assert!(matches!(
+            set_cells(xml, &cells),
+            Err(ExportError::SharedFormula(cell)) if cell == "D2"
+        ));
+
+        let cells = BTreeMap::from([((3, 3), CellValue::Number(1.0))]);
+        assert!(
+            set_cells(xml, &cells)
+                .unwrap()
+                .contains(r#"<c r="D3"><v>1</v></c>"#)
+        );
+    }
+
+    #[test]
+    fn test_set_root_attribute() {
+        assert_eq!(
+            set_root_attribute(r#"<formControlPr objectType="Drop" sel="1"/>"#, "sel", "4")
+                .unwrap(),
+            r#"<formControlPr objectType="Drop" sel="4"/>"#
+        );
+        assert_eq!(
+            set_root_attribute(r#"<formControlPr objectType="Drop"/>"#, "sel", "2").unwrap(),
+            r#"<formControlPr objectType="Drop" sel="2"/>"#
+        );
+    }
+
+    #[test]
+    fn test_full_calc_on_load() {
+        assert_eq!(
+            full_calc_on_load(r#"<workbook><sheets/><calcPr calcId="191029"/></workbook>"#)
+                .unwrap(),
+            r#"<workbook><sheets/><calcPr calcId="191029" fullCalcOnLoad="1"/></workbook>"#
+        );
+        assert_eq!(
+            full_calc_on_load(r#"<workbook><sheets/><externalReferences/><extLst/></workbook>"#)
+                .unwrap(),
+            r#"<workbook><sheets/><externalReferences/><calcPr fullCalcOnLoad="1"/><extLst/></workbook>"#
+        );
+        assert_eq!(
+            full_calc_on_load(r#"<workbook><sheets/><definedNames/></workbook>"#).unwrap(),
+            r#"<workbook><sheets/><definedNames/><calcPr fullCalcOnLoad="1"/></workbook>"#
+        );
+    }
+}
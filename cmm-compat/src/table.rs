@@ -0,0 +1,246 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use roxmltree::{Document, Node};
+
+use crate::{
+    ExportError,
+    sheet::{CellPosition, CellValue, escape, remove_attribute},
+};
+
+const TABLE: &str = "urn:oasis:names:tc:opendocument:xmlns:table:1.0";
+
+const ROWS_REPEATED: &str = "table:number-rows-repeated";
+const COLUMNS_REPEATED: &str = "table:number-columns-repeated";
+
+/// Value attributes of a cell which are replaced by the new value
+const VALUE_ATTRIBUTES: [&str; 10] = [
+    "office:value-type",
+    "office:value",
+    "office:string-value",
+    "office:boolean-value",
+    "office:date-value",
+    "office:time-value",
+    "office:currency",
+    "calcext:value-type",
+    "table:formula",
+    COLUMNS_REPEATED,
+];
+
+/// Sets cell values of the table `name` in the content.xml of an OpenDocument spreadsheet
+/// and returns the new xml. Repeated rows and cells are split up so only the given cells
+/// change, the style of existing cells is kept.
+pub(crate) fn set_table_cells(
+    xml: &str,
+    name: &str,
+    cells: &BTreeMap<CellPosition, CellValue>,
+) -> Result<String, ExportError> {
+    let document = Document::parse(xml)?;
+    let table = document
+        .descendants()
+        .find(|node| is_table(node, "table") && node.attribute((TABLE, "name")) == Some(name))
+        .ok_or_else(|| ExportError::MissingSheet(name.to_owned()))?;
+
+    let mut by_row: BTreeMap<u32, Vec<(u32, &CellValue)>> = BTreeMap::new();
+    for ((row, column), value) in cells {
+        by_row.entry(*row).or_default().push((*column, value));
+    }
+
+    let mut replacements: Vec<(Range<usize>, String)> = Vec::new();
+    let mut first = 1;
+    for row in table
+        .descendants()
+        .filter(|node| is_table(node, "table-row"))
+    {
+        let repeated = repeated(&row, ROWS_REPEATED);
+        let last = first + repeated - 1;
+        let targets: Vec<(u32, &Vec<(u32, &CellValue)>)> = by_row
+            .range(first..=last)
+            .map(|(row, cells)| (*row, cells))
+            .collect();
+        if !targets.is_empty() {
+            replacements.push((row.range(), split_row(xml, &row, first, last, &targets)));
+        }
+        first = last + 1;
+    }
+    if let Some(row) = by_row.keys().find(|row| **row >= first) {
+        return Err(ExportError::Malformed(format!(
+            "Table {name} ends before row {row}"
+        )));
+    }
+
+    let mut result = String::with_capacity(xml.len());
+    let mut cursor = 0;
+    for (range, text) in replacements {
+        result.push_str(&xml[cursor..range.start]);
+        result.push_str(&text);
+        cursor = range.end;
+    }
+    result.push_str(&xml[cursor..]);
+    Ok(result)
+}
+
+fn is_table(node: &Node, name: &str) -> bool {
+    node.tag_name().namespace() == Some(TABLE) && node.tag_name().name() == name
+}
+
+fn repeated(node: &Node, attribute: &str) -> u32 {
+    let name = attribute.trim_start_matches("table:");
+    node.attribute((TABLE, name))
+        .and_then(|repeated| repeated.parse().ok())
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Rows `first..=last` of a repeated row, the target rows get the new cells
+fn split_row(
+    xml: &str,
+    row: &Node,
+    first: u32,
+    last: u32,
+    targets: &[(u32, &Vec<(u32, &CellValue)>)],
+) -> String {
+    let text = &xml[row.range()];
+    let mut result = String::new();
+    let mut cursor = first;
+    for (target, cells) in targets {
+        if *target > cursor {
+            result.push_str(&with_repeated(text, ROWS_REPEATED, target - cursor));
+        }
+        result.push_str(&rebuild_row(xml, row, cells));
+        cursor = target + 1;
+    }
+    if last >= cursor {
+        result.push_str(&with_repeated(text, ROWS_REPEATED, last - cursor + 1));
+    }
+    result
+}
+
+fn rebuild_row(xml: &str, row: &Node, cells: &[(u32, &CellValue)]) -> String {
+    let text = &xml[row.range()];
+    let (start_tag, _) = split_start_tag(&with_repeated(text, ROWS_REPEATED, 1));
+
+    let mut result = start_tag;
+    let mut column = 0;
+    let mut remaining = cells.iter().peekable();
+    for child in row.children() {
+        let child_text = &xml[child.range()];
+        if !(is_table(&child, "table-cell") || is_table(&child, "covered-table-cell")) {
+            result.push_str(child_text);
+            continue;
+        }
+        let repeated = repeated(&child, COLUMNS_REPEATED);
+        let last = column + repeated - 1;
+        let mut cursor = column;
+        while let Some((target, value)) = remaining.next_if(|(target, _)| *target <= last) {
+            if *target > cursor {
+                result.push_str(&with_repeated(
+                    child_text,
+                    COLUMNS_REPEATED,
+                    target - cursor,
+                ));
+            }
+            result.push_str(&cell(child_text, value));
+            cursor = target + 1;
+        }
+        if last >= cursor {
+            result.push_str(&with_repeated(
+                child_text,
+                COLUMNS_REPEATED,
+                last - cursor + 1,
+            ));
+        }
+        column = last + 1;
+    }
+    // Cells after the last existing cell of the row
+    for (target, value) in remaining {
+        if *target > column {
+            result.push_str(&format!(
+                "<table:table-cell {COLUMNS_REPEATED}=\"{}\"/>",
+                target - column
+            ));
+        }
+        result.push_str(&cell("<table:table-cell/>", value));
+        column = target + 1;
+    }
+    result.push_str("</table:table-row>");
+    result
+}
+
+/// The cell with a new value, the other attributes (style, merged cells) are kept
+fn cell(original: &str, value: &CellValue) -> String {
+    let (start_tag, _) = split_start_tag(original);
+    let mut start_tag = VALUE_ATTRIBUTES.iter().fold(start_tag, |tag, attribute| {
+        remove_attribute(&tag, attribute)
+    });
+    start_tag.pop();
+    let name = start_tag[1..]
+        .split(|char: char| char.is_whitespace() || char == '>')
+        .next()
+        .unwrap_or("table:table-cell")
+        .to_owned();
+
+    match value {
+        CellValue::Number(number) => format!(
+            "{start_tag} office:value-type=\"float\" office:value=\"{number}\"><text:p>{number}</text:p></{name}>"
+        ),
+        CellValue::Text(text) => format!(
+            "{start_tag} office:value-type=\"string\">{}</{name}>",
+            text.lines()
+                .map(|line| format!("<text:p>{}</text:p>", escape(line)))
+                .collect::<String>()
+        ),
+    }
+}
+
+/// Start tag as opening tag (self-closing tags are opened) and the rest of the element
+fn split_start_tag(text: &str) -> (String, &str) {
+    let end = text.find('>').unwrap_or(text.len().saturating_sub(1));
+    let tag = &text[..end];
+    match tag.strip_suffix('/') {
+        Some(tag) => (format!("{}>", tag.trim_end()), ""),
+        None => (format!("{tag}>"), text.get(end + 1..).unwrap_or_default()),
+    }
+}
+
+/// The element with the repeat attribute of its start tag set to `count`
+fn with_repeated(text: &str, attribute: &str, count: u32) -> String {
+    let end = text.find('>').unwrap_or(text.len());
+    let tag = remove_attribute(&text[..end], attribute);
+    let (tag, closing) = match tag.strip_suffix('/') {
+        Some(tag) => (tag.trim_end().to_owned(), "/"),
+        None => (tag, ""),
+    };
+    let repeat = if count > 1 {
+        format!(" {attribute}=\"{count}\"")
+    } else {
+        String::new()
+    };
+    format!("{tag}{repeat}{closing}{}", &text[end..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"><table:table table:name="_Output"><table:table-row table:style-name="ro1"><table:table-cell office:value-type="string"><text:p>B 1.1</text:p></table:table-cell><table:table-cell table:style-name="ce1" table:number-columns-repeated="3"/></table:table-row><table:table-row table:number-rows-repeated="4"><table:table-cell table:number-columns-repeated="2"/></table:table-row></table:table></office:document-content>"#;
+
+    #[test]
+    fn test_set_table_cells() {
+        let cells = BTreeMap::from([
+            ((1, 2), CellValue::Number(3.0)),
+            ((3, 1), CellValue::Text(String::from("a & b\nc"))),
+            ((3, 4), CellValue::Number(1.0)),
+        ]);
+        assert_eq!(
+            set_table_cells(CONTENT, "_Output", &cells).unwrap(),
+            r#"<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"><table:table table:name="_Output"><table:table-row table:style-name="ro1"><table:table-cell office:value-type="string"><text:p>B 1.1</text:p></table:table-cell><table:table-cell table:style-name="ce1"/><table:table-cell table:style-name="ce1" office:value-type="float" office:value="3"><text:p>3</text:p></table:table-cell><table:table-cell table:style-name="ce1"/></table:table-row><table:table-row><table:table-cell table:number-columns-repeated="2"/></table:table-row><table:table-row><table:table-cell/><table:table-cell office:value-type="string"><text:p>a &amp; b</text:p><text:p>c</text:p></table:table-cell><table:table-cell table:number-columns-repeated="2"/><table:table-cell office:value-type="float" office:value="1"><text:p>1</text:p></table:table-cell></table:table-row><table:table-row table:number-rows-repeated="2"><table:table-cell table:number-columns-repeated="2"/></table:table-row></table:table></office:document-content>"#
+        );
+    }
+
+    #[test]
+    fn test_table_too_short() {
+        let cells = BTreeMap::from([((9, 0), CellValue::Number(1.0))]);
+        assert!(set_table_cells(CONTENT, "_Output", &cells).is_err());
+        assert!(set_table_cells(CONTENT, "Profile", &cells).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{Cursor, Read, Seek},
+    path::Path,
+};
+
+use calamine::{Data, DataType, Ods, Range, Reader, Xlsx};
+use cmm_core::{cid::Domain, schema::Schema, score::Stats};
+
+use crate::{
+    ImportError, ImportReport, cell, from_ods_reader, from_xlsx_reader, is_nist_mapping,
+    layout::Layout,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ScoreKind {
+    Maturity,
+    Capability,
+}
+
+/// Score computed by the workbook next to the score cmm-core computes from the imported data,
+/// both in percent. Not applicable scores are None.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Parity {
+    domain: Domain,
+    aspect: Option<u8>,
+    kind: ScoreKind,
+    workbook: Option<f64>,
+    core: Option<f64>,
+}
+
+impl Parity {
+    pub fn domain(&self) -> Domain {
+        self.domain
+    }
+
+    /// None for the score of the whole domain
+    pub fn aspect(&self) -> Option<u8> {
+        self.aspect
+    }
+
+    pub fn kind(&self) -> ScoreKind {
+        self.kind
+    }
+
+    pub fn workbook(&self) -> Option<f64> {
+        self.workbook
+    }
+
+    pub fn core(&self) -> Option<f64> {
+        self.core
+    }
+
+    /// Difference of cmm-core to the workbook in percentage points
+    pub fn delta(&self) -> Option<f64> {
+        Some(self.core? - self.workbook?)
+    }
+
+    /// Both scores are within `tolerance` percentage points or both are not applicable
+    pub fn matches(&self, tolerance: f64) -> bool {
+        match (self.workbook, self.core) {
+            (Some(workbook), Some(core)) => (core - workbook).abs() <= tolerance,
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for ScoreKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScoreKind::Maturity => write!(f, "maturity"),
+            ScoreKind::Capability => write!(f, "capability"),
+        }
+    }
+}
+
+/// Compares the scores of a filled xlsx workbook, as last calculated by Excel, with cmm-core
+pub fn verify_xlsx<P: AsRef<Path>>(
+    path: P,
+    schema: &Schema,
+) -> Result<(Vec<Parity>, ImportReport), ImportError> {
+    verify_xlsx_reader(File::open(path)?, schema)
+}
+
+/// Compares the scores of the bytes of a filled xlsx workbook with cmm-core
+pub fn verify_xlsx_reader<R: Read + Seek>(
+    mut reader: R,
+    schema: &Schema,
+) -> Result<(Vec<Parity>, ImportReport), ImportError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let (data, report) = from_xlsx_reader(Cursor::new(bytes.as_slice()), schema)?;
+    let workbook: Xlsx<_> = Xlsx::new(Cursor::new(bytes.as_slice()))?;
+    let parity = verify(workbook, &report, Stats::new(data, schema.clone()))?;
+    Ok((parity, report))
+}
+
+/// Compares the scores of a filled ods workbook, as last calculated by LibreOffice, with cmm-core
+pub fn verify_ods<P: AsRef<Path>>(
+    path: P,
+    schema: &Schema,
+) -> Result<(Vec<Parity>, ImportReport), ImportError> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    let (data, report) = from_ods_reader(Cursor::new(bytes.as_slice()), schema)?;
+    let workbook: Ods<_> = Ods::new(Cursor::new(bytes.as_slice()))?;
+    let parity = verify(workbook, &report, Stats::new(data, schema.clone()))?;
+    Ok((parity, report))
+}
+
+fn verify<RS, W>(
+    mut workbook: W,
+    report: &ImportReport,
+    stats: Stats,
+) -> Result<Vec<Parity>, ImportError>
+where
+    RS: Read + Seek,
+    W: Reader<RS>,
+    ImportError: From<W::Error>,
+{
+    // The import already failed for workbooks without a layout
+    let version = report.version().copied().unwrap_or_default();
+    let layout = version
+        .layout()
+        .ok_or(ImportError::UnsupportedVersion(version))?;
+    let output = workbook.worksheet_range(layout.output_sheet)?;
+    Ok(parity(&output, layout, &stats))
+}
+
+fn parity(output: &Range<Data>, layout: &Layout, stats: &Stats) -> Vec<Parity> {
+    output
+        .rows()
+        .filter(|row| !is_nist_mapping(row, layout))
+        .filter_map(|row| {
+            let (domain, aspect, kind) = score_label(row, layout)?;
+            let score = match (kind, aspect) {
+                (ScoreKind::Maturity, Some(aspect)) => stats.maturity_by_aspect(&domain, aspect),
+                (ScoreKind::Capability, Some(aspect)) => {
+                    stats.capability_by_aspect(&domain, aspect)
+                }
+                (ScoreKind::Maturity, None) => stats.maturity_by_domain(&domain),
+                (ScoreKind::Capability, None) => stats.capability_by_domain(&domain),
+            };
+            Some(Parity {
+                domain,
+                aspect,
+                kind,
+                workbook: cell(row, layout.score_column).as_f64(),
+                core: score.as_percentage(),
+            })
+        })
+        .collect()
+}
+
+/// Score row of the output sheet, e.g. "M 5" of type "M" for the maturity of aspect 5 of
+/// Process or "Services" of type "C" for the capability of the whole domain
+fn score_label(row: &[Data], layout: &Layout) -> Option<(Domain, Option<u8>, ScoreKind)> {
+    let kind = match cell(row, layout.output_type_column).as_string()?.trim() {
+        "M" => ScoreKind::Maturity,
+        "C" => ScoreKind::Capability,
+        _ => return None,
+    };
+    let label = cell(row, layout.output_id_column).as_string()?;
+    let (domain, aspect) = match label.trim().split_once(' ') {
+        Some((prefix, aspect)) => {
+            let domain = match prefix {
+                "B" => Domain::Business,
+                "P" => Domain::People,
+                "M" => Domain::Process,
+                "T" => Domain::Technology,
+                "S" => Domain::Services,
+                _ => return None,
+            };
+            (domain, Some(aspect.parse().ok()?))
+        }
+        None => (label.trim().parse().ok()?, None),
+    };
+    Some((domain, aspect, kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::WorkbookVersion;
+
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let parity = |workbook, core| Parity {
+            domain: Domain::Business,
+            aspect: Some(1),
+            kind: ScoreKind::Maturity,
+            workbook,
+            core,
+        };
+        assert!(parity(Some(45.0), Some(45.004)).matches(0.01));
+        assert!(!parity(Some(45.0), Some(46.0)).matches(0.01));
+        assert!(!parity(Some(0.0), None).matches(0.01));
+        assert!(parity(None, None).matches(0.01));
+        assert_eq!(parity(Some(45.0), Some(46.0)).delta(), Some(1.0));
+    }
+
+    #[test]
+    fn test_score_label() {
+        let layout = WorkbookVersion::default().layout().unwrap();
+        let row = |id: &str, kind: &str| {
+            vec![
+                Data::String(id.to_owned()),
+                Data::Empty,
+                Data::String(kind.to_owned()),
+            ]
+        };
+        assert_eq!(
+            score_label(&row("M 5", "M"), layout),
+            Some((Domain::Process, Some(5), ScoreKind::Maturity))
+        );
+        assert_eq!(
+            score_label(&row("Services", "C"), layout),
+            Some((Domain::Services, None, ScoreKind::Capability))
+        );
+        // Controls and headings are no scores
+        assert_eq!(score_label(&row("B 1.2", "M"), layout), None);
+        assert_eq!(score_label(&row("Business", ""), layout), None);
+    }
+}
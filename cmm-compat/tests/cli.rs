@@ -0,0 +1,64 @@
+mod common;
+
+use std::{fs::write, io::Cursor, path::PathBuf, process::Command};
+
+use cmm_compat::from_xlsx_reader;
+use cmm_core::{
+    answer::{Answer, Detailed},
+    schema::Schema,
+};
+use common::{DETAILED, Workbook};
+
+fn cmm(args: &[&str]) -> Option<i32> {
+    Command::new(env!("CARGO_BIN_EXE_cmm"))
+        .args(args)
+        .output()
+        .unwrap()
+        .status
+        .code()
+}
+
+/// Writes a file to the scratch directory of the integration tests
+fn scratch(name: &str, content: String) -> String {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    write(&path, content).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+#[test]
+fn test_exit_codes() {
+    let schema: Schema = serde_json::from_str(include_str!("../../scheme-2.3.4.json")).unwrap();
+    let mut workbook = Workbook::new("SOC-CMM 2.3.4 basic");
+    workbook.control("Business - BSD", "1.1", "B 1.1", "M", DETAILED);
+    let (mut data, _report) = from_xlsx_reader(Cursor::new(workbook.to_bytes()), &schema).unwrap();
+    let before = scratch("before.toml", toml::to_string(&data).unwrap());
+    data.set_answer(
+        &"Business.1.1".parse().unwrap(),
+        Answer::Detailed(Detailed::Fully),
+    );
+    let after = scratch("after.toml", toml::to_string(&data).unwrap());
+
+    assert_eq!(cmm(&["diff", &before, &before]), Some(0));
+    // Problems found
+    assert_eq!(cmm(&["diff", &before, &after]), Some(1));
+    // Failed commands and usage errors
+    let unknown = scratch("assessment.txt", String::new());
+    assert_eq!(cmm(&["import", &unknown]), Some(2));
+    assert_eq!(cmm(&["score"]), Some(2));
+}
+
+#[test]
+fn test_validate_justifications() {
+    let schema: Schema = serde_json::from_str(include_str!("../../scheme-2.3.4.json")).unwrap();
+    let mut workbook = Workbook::new("SOC-CMM 2.3.4 basic");
+    workbook.control("Business - BSD", "1.1", "B 1.1", "M", DETAILED);
+    let (mut data, _report) = from_xlsx_reader(Cursor::new(workbook.to_bytes()), &schema).unwrap();
+    let charter = "Business.1.1".parse().unwrap();
+    data.set_answer(&charter, Answer::Detailed(Detailed::NotApplicable));
+    let unjustified = scratch("unjustified.toml", toml::to_string(&data).unwrap());
+    data.set_comment(&charter, Some(String::from("Mandated by the holding")));
+    let justified = scratch("justified.toml", toml::to_string(&data).unwrap());
+
+    assert_eq!(cmm(&["validate", &unjustified]), Some(1));
+    assert_eq!(cmm(&["validate", &justified]), Some(0));
+}
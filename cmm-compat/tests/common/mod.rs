@@ -0,0 +1,423 @@
+//! Minimal SOC-CMM 2.3 workbook built in memory, the official workbooks can not be distributed.
+//! It only contains what the import needs: the assessment sheets with their controls and
+//! comment blocks, the output sheet with the scores, the entries of the dropdowns and
+//! a dropdown per control. It is written as xlsx or as ods.
+#![allow(dead_code)]
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{Cursor, Write},
+};
+
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+pub const DETAILED: &str = "_Input!$C$13:$C$17";
+pub const DETAILED_OPTIONAL: &str = "_Input!$C$13:$C$18";
+pub const OCCURENCE: &str = "_Input!$C$39:$C$43";
+pub const BOOL: &str = "_Input!$C$3:$C$4";
+
+const SHEETS: [&str; 26] = [
+    "Business - BSD",
+    "Business - CST",
+    "Business - CHT",
+    "Business - GOV",
+    "Business - PRV",
+    "People - EMP",
+    "People - R&H",
+    "People - PEM",
+    "People - KNM",
+    "People - T&E",
+    "Process - MGT",
+    "Process - O&F",
+    "Process - RPT",
+    "Process - UCM",
+    "Process - DTE",
+    "Technology - SIM",
+    "Technology - NDR",
+    "Technology - EDR",
+    "Technology - A&O",
+    "Services - SCM",
+    "Services - SIM",
+    "Services - A&F",
+    "Services - THR",
+    "Services - HNT",
+    "Services - VUL",
+    "Services - LOG",
+];
+
+/// Entries of the input ranges, first row (1-based) and texts
+const INPUTS: [(u32, &[&str]); 4] = [
+    (3, &["No", "Yes"]),
+    (
+        13,
+        &[
+            "No",
+            "Partially",
+            "Averagely",
+            "Mostly",
+            "Fully",
+            "Not Required",
+        ],
+    ),
+    (39, &["Never", "Sometimes", "Averagely", "Mostly", "Always"]),
+    (45, &["No", "Somewhat", "Averagely", "Mostly", "Fully"]),
+];
+
+/// First row of the controls and of the comment block of an assessment sheet (1-based)
+const FIRST_CONTROL_ROW: u32 = 10;
+const COMMENTS_ROW: u32 = 100;
+
+#[derive(Clone)]
+pub enum Value {
+    Text(String),
+    Number(f64),
+}
+
+struct FormControl {
+    link: String,
+    input: String,
+    selected: u32,
+}
+
+pub struct Workbook {
+    title: String,
+    /// Sheet name -> (row (1-based), column (0-based)) -> value
+    sheets: BTreeMap<String, BTreeMap<(u32, u32), Value>>,
+    form_controls: Vec<FormControl>,
+    controls_per_sheet: HashMap<String, u32>,
+    output_rows: u32,
+}
+
+impl Workbook {
+    /// Empty workbook with the release in its title, e.g. "SOC-CMM 2.3.4 basic"
+    pub fn new(title: &str) -> Self {
+        let mut workbook = Self {
+            title: title.to_owned(),
+            sheets: BTreeMap::new(),
+            form_controls: Vec::new(),
+            controls_per_sheet: HashMap::new(),
+            output_rows: 1,
+        };
+        for sheet in SHEETS {
+            workbook.set(sheet, 1, 0, Value::Text(sheet.to_owned()));
+            workbook.set(
+                sheet,
+                COMMENTS_ROW,
+                1,
+                Value::Text(String::from("Comments and/or Remarks")),
+            );
+        }
+        workbook.set("_Output", 1, 0, Value::Text(String::from("Output")));
+        // calamine misplaces the rows of ods tables which don't start in column A
+        workbook.set("_Input", 1, 0, Value::Text(String::from("Input")));
+        for (first, entries) in INPUTS {
+            for (row, entry) in (first..).zip(entries) {
+                workbook.set("_Input", row, 2, Value::Text(entry.to_string()));
+            }
+        }
+        workbook
+    }
+
+    pub fn set(&mut self, sheet: &str, row: u32, column: u32, value: Value) -> &mut Self {
+        self.sheets
+            .entry(sheet.to_owned())
+            .or_default()
+            .insert((row, column), value);
+        self
+    }
+
+    /// Adds a control to an assessment sheet, e.g. "1.2" of "Business - BSD", with its comment
+    /// row and its row in the output sheet, e.g. "B 1.2" of type "M". Returns the output row.
+    pub fn control(
+        &mut self,
+        sheet: &str,
+        id: &str,
+        output_id: &str,
+        kind: &str,
+        input: &str,
+    ) -> u32 {
+        let count = self.controls_per_sheet.entry(sheet.to_owned()).or_default();
+        let row = *count;
+        *count += 1;
+        self.set(
+            sheet,
+            FIRST_CONTROL_ROW + row,
+            1,
+            Value::Text(format!("{id} Question")),
+        );
+        self.set(
+            sheet,
+            COMMENTS_ROW + 1 + row,
+            11,
+            Value::Text(id.to_owned()),
+        );
+
+        self.output_rows += 1;
+        let output_row = self.output_rows;
+        self.set("_Output", output_row, 0, Value::Text(output_id.to_owned()));
+        self.set("_Output", output_row, 2, Value::Text(kind.to_owned()));
+        self.set("_Output", output_row, 3, Value::Number(1.0));
+        self.form_controls.push(FormControl {
+            link: format!("_Output!$D${output_row}"),
+            input: input.to_owned(),
+            selected: 1,
+        });
+        output_row
+    }
+
+    /// Adds a score computed by the workbook to the output sheet, e.g. "B 1" or "Business" of
+    /// type "M" in percent
+    pub fn score(&mut self, label: &str, kind: &str, value: f64) -> &mut Self {
+        self.output_rows += 1;
+        let output_row = self.output_rows;
+        self.set("_Output", output_row, 0, Value::Text(label.to_owned()));
+        self.set("_Output", output_row, 2, Value::Text(kind.to_owned()));
+        self.set("_Output", output_row, 12, Value::Number(value))
+    }
+
+    /// Zipped xlsx package
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let mut file = |name: &str, content: String| {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        };
+
+        let sheets: Vec<_> = self.sheets.iter().collect();
+        let mut content_types = String::from(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>"#,
+        );
+        let mut workbook = String::from(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets>"#,
+        );
+        let mut relationships = String::from(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+        );
+        for (i, (name, _cells)) in sheets.iter().enumerate() {
+            let id = i + 1;
+            content_types.push_str(&format!(
+                r#"<Override PartName="/xl/worksheets/sheet{id}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#
+            ));
+            workbook.push_str(&format!(
+                r#"<sheet name="{}" sheetId="{id}" r:id="rId{id}"/>"#,
+                escape(name)
+            ));
+            relationships.push_str(&format!(
+                r#"<Relationship Id="rId{id}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet{id}.xml"/>"#
+            ));
+        }
+        content_types.push_str("</Types>");
+        workbook.push_str(r#"</sheets><calcPr calcId="191029"/></workbook>"#);
+        relationships.push_str("</Relationships>");
+
+        file("[Content_Types].xml", content_types);
+        file(
+            "_rels/.rels",
+            String::from(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/></Relationships>"#,
+            ),
+        );
+        file(
+            "docProps/core.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>{}</dc:title></cp:coreProperties>"#,
+                escape(&self.title)
+            ),
+        );
+        file("xl/workbook.xml", workbook);
+        file("xl/_rels/workbook.xml.rels", relationships);
+        for (i, (_name, cells)) in sheets.iter().enumerate() {
+            file(
+                &format!("xl/worksheets/sheet{}.xml", i + 1),
+                worksheet(cells),
+            );
+        }
+        for (i, form_control) in self.form_controls.iter().enumerate() {
+            file(
+                &format!("xl/ctrlProps/ctrlProp{}.xml", i + 1),
+                format!(
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><formControlPr xmlns="http://schemas.microsoft.com/office/spreadsheetml/2009/9/main" objectType="Drop" dropStyle="combo" fmlaLink="{}" fmlaRange="{}" sel="{}"/>"#,
+                    escape(&form_control.link),
+                    escape(&form_control.input),
+                    form_control.selected
+                ),
+            );
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    /// Zipped ods package, `linkage` is the list linkage type of the list boxes, e.g.
+    /// "selection-index", or None for the default which links the text of the entry
+    pub fn to_ods_bytes(&self, linkage: Option<&str>) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let mut file = |name: &str, content: String| {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        };
+
+        let mut sheets = self.sheets.clone();
+        let mut forms = String::new();
+        for (i, form_control) in self.form_controls.iter().enumerate() {
+            let link = ods_reference(&form_control.link);
+            let input = ods_reference(&form_control.input);
+            let linkage = linkage
+                .map(|linkage| format!(r#" form:list-linkage-type="{linkage}""#))
+                .unwrap_or_default();
+            forms.push_str(&format!(
+                r#"<form:listbox form:name="List Box {}" form:linked-cell="{link}" form:source-cell-range="{input}"{linkage}/>"#,
+                i + 1
+            ));
+            if linkage.is_empty() {
+                // The linked cell holds the text of the entry instead of its position
+                let (row, entry_row) =
+                    (link_row(&form_control.link), link_row(&form_control.input));
+                let entry = match &sheets["_Input"][&(entry_row + form_control.selected - 1, 2)] {
+                    Value::Text(text) => text.clone(),
+                    Value::Number(number) => number.to_string(),
+                };
+                sheets
+                    .get_mut("_Output")
+                    .unwrap()
+                    .insert((row, 3), Value::Text(entry));
+            }
+        }
+
+        let tables: String = sheets
+            .iter()
+            .map(|(name, cells)| {
+                let forms = match name.as_str() {
+                    "_Output" => format!(r#"<office:forms><form:form form:name="Form">{forms}</form:form></office:forms>"#),
+                    _ => String::new(),
+                };
+                format!(
+                    r#"<table:table table:name="{}">{forms}{}</table:table>"#,
+                    escape(name),
+                    table(cells)
+                )
+            })
+            .collect();
+
+        file(
+            "mimetype",
+            String::from("application/vnd.oasis.opendocument.spreadsheet"),
+        );
+        file(
+            "META-INF/manifest.xml",
+            String::from(
+                r#"<?xml version="1.0" encoding="UTF-8"?><manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.3"><manifest:file-entry manifest:full-path="/" manifest:media-type="application/vnd.oasis.opendocument.spreadsheet"/><manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/><manifest:file-entry manifest:full-path="meta.xml" manifest:media-type="text/xml"/></manifest:manifest>"#,
+            ),
+        );
+        file(
+            "meta.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?><office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:dc="http://purl.org/dc/elements/1.1/" office:version="1.3"><office:meta><dc:title>{}</dc:title></office:meta></office:document-meta>"#,
+                escape(&self.title)
+            ),
+        );
+        file(
+            "content.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?><office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:form="urn:oasis:names:tc:opendocument:xmlns:form:1.0" office:version="1.3"><office:body><office:spreadsheet>{tables}</office:spreadsheet></office:body></office:document-content>"#
+            ),
+        );
+        zip.finish().unwrap().into_inner()
+    }
+}
+
+/// Rows of an ods table, gaps are filled with repeated empty rows and cells
+fn table(cells: &BTreeMap<(u32, u32), Value>) -> String {
+    let mut rows: BTreeMap<u32, Vec<(u32, &Value)>> = BTreeMap::new();
+    for ((row, column), value) in cells {
+        rows.entry(*row).or_default().push((*column, value));
+    }
+    let mut table = String::new();
+    let mut next_row = 1;
+    for (row, cells) in rows {
+        if row > next_row {
+            table.push_str(&format!(
+                r#"<table:table-row table:number-rows-repeated="{}"><table:table-cell/></table:table-row>"#,
+                row - next_row
+            ));
+        }
+        table.push_str("<table:table-row>");
+        let mut next_column = 0;
+        for (column, value) in cells {
+            if column > next_column {
+                table.push_str(&format!(
+                    r#"<table:table-cell table:number-columns-repeated="{}"/>"#,
+                    column - next_column
+                ));
+            }
+            table.push_str(&match value {
+                Value::Text(text) => format!(
+                    r#"<table:table-cell office:value-type="string"><text:p>{}</text:p></table:table-cell>"#,
+                    escape(text)
+                ),
+                Value::Number(number) => format!(
+                    r#"<table:table-cell office:value-type="float" office:value="{number}"><text:p>{number}</text:p></table:table-cell>"#
+                ),
+            });
+            next_column = column + 1;
+        }
+        table.push_str("</table:table-row>");
+        next_row = row + 1;
+    }
+    table
+}
+
+/// "_Output!$D$12" -> "_Output.D12", "_Input!$C$13:$C$17" -> "_Input.C13:_Input.C17"
+fn ods_reference(reference: &str) -> String {
+    let (sheet, cells) = reference.split_once('!').unwrap();
+    cells
+        .replace('$', "")
+        .split(':')
+        .map(|cell| format!("{sheet}.{cell}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// First row of a reference, e.g. "_Input!$C$13:$C$17" -> 13
+fn link_row(reference: &str) -> u32 {
+    let cell = reference
+        .split_once('!')
+        .unwrap()
+        .1
+        .split(':')
+        .next()
+        .unwrap();
+    cell.rsplit('$').next().unwrap().parse().unwrap()
+}
+
+fn worksheet(cells: &BTreeMap<(u32, u32), Value>) -> String {
+    let mut rows: BTreeMap<u32, String> = BTreeMap::new();
+    for ((row, column), value) in cells {
+        let reference = format!("{}{row}", column_name(*column));
+        let cell = match value {
+            Value::Text(text) => format!(
+                r#"<c r="{reference}" t="inlineStr"><is><t>{}</t></is></c>"#,
+                escape(text)
+            ),
+            Value::Number(number) => format!(r#"<c r="{reference}"><v>{number}</v></c>"#),
+        };
+        rows.entry(*row).or_default().push_str(&cell);
+    }
+    let rows: String = rows
+        .into_iter()
+        .map(|(row, cells)| format!(r#"<row r="{row}">{cells}</row>"#))
+        .collect();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>{rows}</sheetData></worksheet>"#
+    )
+}
+
+/// Columns up to Z are enough for the layout
+fn column_name(column: u32) -> char {
+    (b'A' + column as u8) as char
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
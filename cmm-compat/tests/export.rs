@@ -0,0 +1,65 @@
+mod common;
+
+use std::io::Cursor;
+
+use cmm_compat::{from_xlsx_reader, to_xlsx_writer};
+use cmm_core::{
+    answer::{Answer, Detailed, DetailedOptional, Occurence},
+    cid::CID,
+    schema::Schema,
+};
+use common::{BOOL, DETAILED, DETAILED_OPTIONAL, OCCURENCE, Workbook};
+
+fn template() -> Vec<u8> {
+    let mut workbook = Workbook::new("SOC-CMM 2.3.4 basic");
+    workbook.control("Business - BSD", "1.1", "B 1.1", "M", DETAILED);
+    workbook.control("Business - BSD", "1.2", "B 1.2", "M", OCCURENCE);
+    workbook.control("Business - CHT", "3.2.1", "B 3.2.1", "", BOOL);
+    workbook.control(
+        "Technology - SIM",
+        "1.7.1",
+        "T 1.7.1",
+        "C",
+        DETAILED_OPTIONAL,
+    );
+    workbook.to_bytes()
+}
+
+#[test]
+fn test_export_import_round_trip() {
+    let schema: Schema = serde_json::from_str(include_str!("../../scheme-2.3.4.json")).unwrap();
+    let (mut data, _report) = from_xlsx_reader(Cursor::new(template()), &schema).unwrap();
+    let charter: CID = "Business.1.1".parse().unwrap();
+    assert_eq!(
+        data.control(&charter).unwrap().answer(),
+        &Answer::Detailed(Detailed::No)
+    );
+
+    let answers = [
+        ("Business.1.1", Answer::Detailed(Detailed::Fully)),
+        ("Business.1.2", Answer::Occurence(Occurence::Sometimes)),
+        ("Business.3.2.1", Answer::Bool(true)),
+        (
+            "Technology.1.7.1",
+            Answer::DetailedOptional(DetailedOptional::Partially),
+        ),
+    ];
+    for (cid, answer) in &answers {
+        data.set_answer(&cid.parse().unwrap(), answer.clone());
+    }
+    data.set_comment(&charter, Some(String::from("Charter & mandate <signed>")));
+
+    let mut exported = Cursor::new(Vec::new());
+    to_xlsx_writer(Cursor::new(template()), &data, &mut exported).unwrap();
+    let (imported, _report) =
+        from_xlsx_reader(Cursor::new(exported.into_inner()), &schema).unwrap();
+
+    for (cid, answer) in &answers {
+        let control = imported.control(&cid.parse().unwrap()).unwrap();
+        assert_eq!(control.answer(), answer, "{cid}");
+    }
+    assert_eq!(
+        imported.control(&charter).unwrap().comment().as_deref(),
+        Some("Charter & mandate <signed>")
+    );
+}
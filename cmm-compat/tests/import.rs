@@ -0,0 +1,38 @@
+mod common;
+
+use std::io::Cursor;
+
+use cmm_compat::{Edition, ImportError, WorkbookVersion, from_xlsx_reader};
+use cmm_core::schema::Schema;
+use common::{DETAILED, Value, Workbook};
+
+#[test]
+fn test_detect_version() {
+    let schema: Schema = serde_json::from_str(include_str!("../../scheme-2.3.4.json")).unwrap();
+    let mut workbook = Workbook::new("SOC-CMM 2.3.4 - Advanced");
+    workbook.control("Business - BSD", "1.1", "B 1.1", "M", DETAILED);
+    // Texts of the sheets don't count
+    workbook.set(
+        "Business - BSD",
+        2,
+        1,
+        Value::Text(String::from("Rephrased since SOC-CMM 2.2 basic")),
+    );
+    let (_data, report) = from_xlsx_reader(Cursor::new(workbook.to_bytes()), &schema).unwrap();
+    assert_eq!(
+        report.version(),
+        Some(&WorkbookVersion::new((2, 3, 4), Edition::Advanced))
+    );
+}
+
+#[test]
+fn test_unsupported_version() {
+    let schema: Schema = serde_json::from_str(include_str!("../../scheme-2.3.4.json")).unwrap();
+    let mut workbook = Workbook::new("SOC-CMM 2.2 - Advanced");
+    workbook.control("Business - BSD", "1.1", "B 1.1", "M", DETAILED);
+    let result = from_xlsx_reader(Cursor::new(workbook.to_bytes()), &schema);
+    assert!(matches!(
+        result,
+        Err(ImportError::UnsupportedVersion(version)) if version.version() == (2, 2, 0)
+    ));
+}
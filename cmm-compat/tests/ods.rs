@@ -0,0 +1,93 @@
+mod common;
+
+use std::io::Cursor;
+
+use cmm_compat::{ImportReport, ImportWarning, from_ods_reader, to_ods_writer};
+use cmm_core::{
+    answer::{Answer, Detailed, DetailedOptional, Occurence},
+    cid::CID,
+    schema::Schema,
+};
+use common::{BOOL, DETAILED, DETAILED_OPTIONAL, OCCURENCE, Workbook};
+
+fn template(linkage: Option<&str>) -> Vec<u8> {
+    let mut workbook = Workbook::new("SOC-CMM 2.3.4 basic");
+    workbook.control("Business - BSD", "1.1", "B 1.1", "M", DETAILED);
+    workbook.control("Business - BSD", "1.2", "B 1.2", "M", OCCURENCE);
+    workbook.control("Business - CHT", "3.2.1", "B 3.2.1", "", BOOL);
+    workbook.control(
+        "Technology - SIM",
+        "1.7.1",
+        "T 1.7.1",
+        "C",
+        DETAILED_OPTIONAL,
+    );
+    workbook.to_ods_bytes(linkage)
+}
+
+/// The workbook only contains a few controls, only the list boxes have to be fine
+fn assert_list_boxes(report: &ImportReport) {
+    let warnings: Vec<_> = report
+        .warnings()
+        .iter()
+        .filter(|warning| {
+            matches!(
+                warning,
+                ImportWarning::UnknownListEntry { .. }
+                    | ImportWarning::OutdatedFormControl { .. }
+                    | ImportWarning::MalformedFormControl(_)
+            )
+        })
+        .collect();
+    assert!(warnings.is_empty(), "{warnings:?}");
+}
+
+fn round_trip(linkage: Option<&str>) {
+    let schema: Schema = serde_json::from_str(include_str!("../../scheme-2.3.4.json")).unwrap();
+    let (mut data, report) = from_ods_reader(Cursor::new(template(linkage)), &schema).unwrap();
+    assert_list_boxes(&report);
+    let charter: CID = "Business.1.1".parse().unwrap();
+    assert_eq!(
+        data.control(&charter).unwrap().answer(),
+        &Answer::Detailed(Detailed::No)
+    );
+
+    let answers = [
+        ("Business.1.1", Answer::Detailed(Detailed::Fully)),
+        ("Business.1.2", Answer::Occurence(Occurence::Sometimes)),
+        ("Business.3.2.1", Answer::Bool(true)),
+        (
+            "Technology.1.7.1",
+            Answer::DetailedOptional(DetailedOptional::NotRequired),
+        ),
+    ];
+    for (cid, answer) in &answers {
+        data.set_answer(&cid.parse().unwrap(), answer.clone());
+    }
+    data.set_comment(&charter, Some(String::from("Charter & mandate <signed>")));
+
+    let mut exported = Cursor::new(Vec::new());
+    to_ods_writer(Cursor::new(template(linkage)), &data, &mut exported).unwrap();
+    let (imported, report) = from_ods_reader(Cursor::new(exported.into_inner()), &schema).unwrap();
+    assert_list_boxes(&report);
+
+    for (cid, answer) in &answers {
+        let control = imported.control(&cid.parse().unwrap()).unwrap();
+        assert_eq!(control.answer(), answer, "{cid}");
+    }
+    assert_eq!(
+        imported.control(&charter).unwrap().comment().as_deref(),
+        Some("Charter & mandate <signed>")
+    );
+}
+
+#[test]
+fn test_round_trip_selection() {
+    // The default linkage, the linked cell holds the text of the entry
+    round_trip(None);
+}
+
+#[test]
+fn test_round_trip_selection_index() {
+    round_trip(Some("selection-index"));
+}
@@ -0,0 +1,179 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+use strum::VariantArray;
+
+use crate::CmmError;
+
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    VariantArray,
+    strum::Display,
+    strum::EnumString,
+)]
+pub enum ActionStatus {
+    #[default]
+    Open,
+    #[strum(serialize = "InProgress", to_string = "In Progress")]
+    InProgress,
+    Done,
+}
+
+/// Calendar date in ISO format, e.g. 2025-06-30, as set by a date input
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, SerializeDisplay, DeserializeFromStr,
+)]
+pub struct DueDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+/// Improvement action to close the gap of a single control
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+pub struct Action {
+    #[serde(default)]
+    owner: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due: Option<DueDate>,
+    #[serde(default)]
+    status: ActionStatus,
+    #[serde(default)]
+    description: String,
+}
+
+impl Action {
+    pub fn new(owner: String, due: Option<DueDate>, status: ActionStatus, description: String) -> Self {
+        Self {
+            owner,
+            due,
+            status,
+            description,
+        }
+    }
+
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    pub fn set_owner(&mut self, owner: String) {
+        self.owner = owner;
+    }
+
+    pub fn due(&self) -> Option<&DueDate> {
+        self.due.as_ref()
+    }
+
+    pub fn set_due(&mut self, due: Option<DueDate>) {
+        self.due = due;
+    }
+
+    pub fn status(&self) -> ActionStatus {
+        self.status
+    }
+
+    pub fn set_status(&mut self, status: ActionStatus) {
+        self.status = status;
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn set_description(&mut self, description: String) {
+        self.description = description;
+    }
+}
+
+impl DueDate {
+    pub fn new(year: u16, month: u8, day: u8) -> crate::Result<Self> {
+        let leap_year =
+            year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap_year => 29,
+            2 => 28,
+            _ => 0,
+        };
+        if !(1..=days).contains(&day) {
+            return Err(CmmError::InvalidDate(format!(
+                "{year:04}-{month:02}-{day:02}"
+            )));
+        }
+        Ok(Self { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl FromStr for DueDate {
+    type Err = CmmError;
+
+    /// Only yyyy-mm-dd is accepted
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CmmError::InvalidDate(s.to_owned());
+        let valid = s.len() == 10
+            && s.char_indices().all(|(i, char)| match i {
+                4 | 7 => char == '-',
+                _ => char.is_ascii_digit(),
+            });
+        if !valid {
+            return Err(invalid());
+        }
+        Self::new(
+            s[..4].parse().map_err(|_| invalid())?,
+            s[5..7].parse().map_err(|_| invalid())?,
+            s[8..].parse().map_err(|_| invalid())?,
+        )
+    }
+}
+
+impl Display for DueDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_due_date() {
+        let date: DueDate = "2024-02-29".parse().unwrap();
+        assert_eq!(date, DueDate::new(2024, 2, 29).unwrap());
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert!("2023-02-29".parse::<DueDate>().is_err());
+        assert!("2025-13-01".parse::<DueDate>().is_err());
+        assert!("2025-6-30".parse::<DueDate>().is_err());
+        assert!("30.06.2025".parse::<DueDate>().is_err());
+        assert!("".parse::<DueDate>().is_err());
+        assert!(DueDate::new(2025, 6, 30).unwrap() < "2026-01-01".parse().unwrap());
+    }
+
+    #[test]
+    fn test_invalid_due_date_is_rejected() {
+        assert!(toml::from_str::<Action>("due = \"next week\"").is_err());
+        let action: Action = toml::from_str("due = \"2025-06-30\"").unwrap();
+        assert_eq!(action.due(), Some(&DueDate::new(2025, 6, 30).unwrap()));
+    }
+}
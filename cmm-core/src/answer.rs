@@ -28,6 +28,8 @@ pub enum Satisfaction {
     Averagely = 3,
     Mostly = 4,
     Fully = 5,
+    #[strum(serialize = "NotApplicable", to_string = "Not Applicable")]
+    NotApplicable = 6,
 }
 impl Default for Satisfaction {
     fn default() -> Self {
@@ -54,6 +56,8 @@ pub enum Occurence {
     Averagely = 3,
     Mostly = 4,
     Always = 5,
+    #[strum(serialize = "NotApplicable", to_string = "Not Applicable")]
+    NotApplicable = 6,
 }
 impl Default for Occurence {
     fn default() -> Self {
@@ -81,6 +85,8 @@ pub enum Detailed {
     Averagely = 3,
     Mostly = 4,
     Fully = 5,
+    #[strum(serialize = "NotApplicable", to_string = "Not Applicable")]
+    NotApplicable = 6,
 }
 impl Default for Detailed {
     fn default() -> Self {
@@ -136,9 +142,59 @@ impl Answer {
         matches!(
             self,
             Answer::Satisfaction(_) | Answer::Occurence(_) | Answer::Detailed(_)
+        ) && !self.is_not_applicable()
+    }
+    /// Maturity answers which do not apply to the SOC, they require a justification
+    pub fn is_not_applicable(&self) -> bool {
+        matches!(
+            self,
+            Answer::Satisfaction(Satisfaction::NotApplicable)
+                | Answer::Occurence(Occurence::NotApplicable)
+                | Answer::Detailed(Detailed::NotApplicable)
         )
     }
+    /// The lowest possible answer (No / Never / false)
+    pub fn is_negative(&self) -> bool {
+        !matches!(self, Answer::Any(_)) && self.is_default()
+    }
+    /// The not applicable counterpart of this answer, answers without one are returned unchanged
+    pub fn to_not_applicable(&self) -> Answer {
+        match self {
+            Answer::Satisfaction(_) => Answer::Satisfaction(Satisfaction::NotApplicable),
+            Answer::Occurence(_) => Answer::Occurence(Occurence::NotApplicable),
+            Answer::Detailed(_) => Answer::Detailed(Detailed::NotApplicable),
+            Answer::DetailedOptional(_) => Answer::DetailedOptional(DetailedOptional::NotRequired),
+            answer => answer.clone(),
+        }
+    }
+    /// Limits a scored answer to the given level, not applicable answers are returned unchanged
+    pub fn capped(&self, level: u32) -> Answer {
+        if self.is_not_applicable()
+            || *self == Answer::DetailedOptional(DetailedOptional::NotRequired)
+        {
+            return self.clone();
+        }
+        let level = level.clamp(1, 5) as usize;
+        match self {
+            Answer::Satisfaction(satisfaction) if *satisfaction as usize > level => {
+                Answer::Satisfaction(Satisfaction::from_repr(level).unwrap_or_default())
+            }
+            Answer::Occurence(occurence) if *occurence as usize > level => {
+                Answer::Occurence(Occurence::from_repr(level).unwrap_or_default())
+            }
+            Answer::Detailed(detailed) if *detailed as usize > level => {
+                Answer::Detailed(Detailed::from_repr(level).unwrap_or_default())
+            }
+            Answer::DetailedOptional(detailed_optional) if *detailed_optional as usize > level => {
+                Answer::DetailedOptional(DetailedOptional::from_repr(level).unwrap_or_default())
+            }
+            answer => answer.clone(),
+        }
+    }
     pub fn maturity_score(&self) -> Option<u32> {
+        if self.is_not_applicable() {
+            return None;
+        }
         match self {
             Answer::Satisfaction(satisfaction) => Some(*satisfaction as u32),
             Answer::Occurence(occurence) => Some(*occurence as u32),
@@ -146,6 +202,14 @@ impl Answer {
             _ => None,
         }
     }
+    /// Maturity or capability level (1-5), None if the answer is not scored
+    pub fn level(&self) -> Option<u32> {
+        self.maturity_score().or_else(|| {
+            self.capability_in_scope()
+                .then(|| self.capability_score())
+                .flatten()
+        })
+    }
     pub fn capability_score(&self) -> Option<u32> {
         match self {
             Answer::DetailedOptional(detailed_optional) => Some(*detailed_optional as u32),
@@ -293,4 +357,39 @@ mod tests {
         assert!(Answer::Any(String::from("Hello")).type_eq(&Answer::Any(String::new())));
         assert!(Answer::Detailed(Detailed::Averagely).type_eq(&Answer::Detailed(Detailed::No)));
     }
+
+    #[test]
+    fn test_not_applicable() {
+        let answer = Answer::Detailed(Detailed::NotApplicable);
+        assert!(answer.is_maturity());
+        assert!(!answer.maturity_in_scope());
+        assert_eq!(answer.maturity_score(), None);
+        assert!(answer.variant_eq("Not Applicable"));
+        assert_eq!(
+            Answer::Occurence(Occurence::Never)
+                .extend_from_variant("Not Applicable")
+                .unwrap(),
+            Answer::Occurence(Occurence::NotApplicable)
+        );
+    }
+
+    #[test]
+    fn test_capped() {
+        assert_eq!(
+            Answer::Detailed(Detailed::Fully).capped(2),
+            Answer::Detailed(Detailed::Partially)
+        );
+        assert_eq!(
+            Answer::Occurence(Occurence::Sometimes).capped(3),
+            Answer::Occurence(Occurence::Sometimes)
+        );
+        assert_eq!(
+            Answer::Detailed(Detailed::NotApplicable).capped(2),
+            Answer::Detailed(Detailed::NotApplicable)
+        );
+        assert_eq!(
+            Answer::DetailedOptional(DetailedOptional::NotRequired).capped(1),
+            Answer::DetailedOptional(DetailedOptional::NotRequired)
+        );
+    }
 }
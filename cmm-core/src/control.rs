@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{answer::Answer, schema::ControlSchema};
+use crate::{action::Action, answer::Answer, schema::ControlSchema};
 use std::ops::Not;
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -13,6 +13,68 @@ pub struct Control {
     bookmark: bool,
 
     comment: Option<String>,
+
+    /// Discussion thread, the single `comment` above is kept for older files and imports
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    comments: Vec<Comment>,
+
+    /// Free-form labels to organise follow-up work, e.g. "quick-win"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    tags: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    action: Option<Action>,
+
+    /// References to documents or artefacts supporting the answer, e.g. "ISMS-POL-01 v2.3"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    evidence: Vec<String>,
+}
+
+/// A single attributed entry in the discussion thread of a control
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    author: String,
+    /// RFC 3339 timestamp, e.g. 2025-01-31T12:00:00.000Z
+    timestamp: String,
+    text: String,
+
+    #[serde(skip_serializing_if = "<&bool>::not")]
+    #[serde(default)]
+    resolved: bool,
+}
+
+impl Comment {
+    pub fn new(author: String, timestamp: String, text: String) -> Self {
+        Self {
+            author,
+            timestamp,
+            text,
+            resolved: false,
+        }
+    }
+
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    pub fn timestamp(&self) -> &str {
+        &self.timestamp
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn resolved(&self) -> bool {
+        self.resolved
+    }
+
+    pub fn toggle_resolved(&mut self) {
+        self.resolved = !self.resolved;
+    }
 }
 
 impl Control {
@@ -21,6 +83,10 @@ impl Control {
             comment,
             answer,
             bookmark: false,
+            comments: Vec::new(),
+            tags: Vec::new(),
+            action: None,
+            evidence: Vec::new(),
         }
     }
 
@@ -40,6 +106,46 @@ impl Control {
         self.comment = comment;
     }
 
+    pub fn comments(&self) -> &Vec<Comment> {
+        &self.comments
+    }
+
+    pub fn add_comment(&mut self, comment: Comment) {
+        self.comments.push(comment);
+    }
+
+    pub fn remove_comment(&mut self, index: usize) {
+        if index < self.comments.len() {
+            self.comments.remove(index);
+        }
+    }
+
+    pub fn toggle_comment_resolved(&mut self, index: usize) {
+        if let Some(comment) = self.comments.get_mut(index) {
+            comment.toggle_resolved();
+        }
+    }
+
+    pub fn open_comments(&self) -> usize {
+        self.comments
+            .iter()
+            .filter(|comment| !comment.resolved())
+            .count()
+    }
+
+    /// Either the single comment or the discussion thread contains something
+    pub fn has_comment(&self) -> bool {
+        self.comment
+            .as_ref()
+            .is_some_and(|comment| !comment.trim().is_empty())
+            || !self.comments.is_empty()
+    }
+
+    /// Not applicable answers have to be justified with a comment
+    pub fn missing_justification(&self) -> bool {
+        self.answer.is_not_applicable() && !self.has_comment()
+    }
+
     pub fn bookmark(&self) -> bool {
         self.bookmark
     }
@@ -48,8 +154,61 @@ impl Control {
         self.bookmark = !self.bookmark;
     }
 
+    pub fn tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Tags are trimmed, empty and duplicate tags are ignored
+    pub fn add_tag(&mut self, tag: &str) {
+        let tag = tag.trim();
+        if tag.is_empty() || self.has_tag(tag) {
+            return;
+        }
+        self.tags.push(tag.to_owned());
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) {
+        let tag = tag.trim();
+        self.tags.retain(|t| t != tag);
+    }
+
+    pub fn action(&self) -> Option<&Action> {
+        self.action.as_ref()
+    }
+
+    pub fn set_action(&mut self, action: Option<Action>) {
+        self.action = action;
+    }
+
+    pub fn evidence(&self) -> &Vec<String> {
+        &self.evidence
+    }
+
+    /// References are trimmed, empty and duplicate references are ignored
+    pub fn add_evidence(&mut self, reference: &str) {
+        let reference = reference.trim();
+        if reference.is_empty() || self.evidence.iter().any(|r| r == reference) {
+            return;
+        }
+        self.evidence.push(reference.to_owned());
+    }
+
+    pub fn remove_evidence(&mut self, reference: &str) {
+        self.evidence.retain(|r| r != reference);
+    }
+
     pub fn is_default(&self) -> bool {
-        self.answer.is_default() && self.comment.is_none() && !self.bookmark
+        self.answer.is_default()
+            && self.comment.is_none()
+            && self.comments.is_empty()
+            && self.tags.is_empty()
+            && self.action.is_none()
+            && self.evidence.is_empty()
+            && !self.bookmark
     }
 }
 
@@ -61,6 +220,10 @@ impl TryFrom<&ControlSchema> for Control {
             answer: Answer::try_from(value.control_type())?,
             bookmark: false,
             comment: None,
+            comments: Vec::new(),
+            tags: Vec::new(),
+            action: None,
+            evidence: Vec::new(),
         })
     }
 }
@@ -1,24 +1,53 @@
 use std::collections::HashMap;
+use std::ops::Not;
 
 use indexmap::IndexMap;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    action::Action,
     answer::Answer,
     cid::{CID, Domain},
-    control::Control,
+    control::{Comment, Control},
+    narrative::{Narrative, NarrativeSection},
+    policy::{Policy, Violation},
     schema::Schema,
     score::Score,
+    CmmError,
 };
 
 /// Only contains the soc-cmm values at its most simple form (CID->Control)
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct SOCData {
     notes: Option<String>,
+
+    /// Aspects or sections which are not part of a scoped assessment, including all their children
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    out_of_scope: Vec<CID>,
+
+    /// Finalized assessments are complete and satisfy the policy
+    #[serde(skip_serializing_if = "<&bool>::not")]
+    #[serde(default)]
+    finalized: bool,
+
     #[serde(default = "IndexMap::new")]
     profile: IndexMap<String, String>,
 
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default = "IndexMap::new")]
+    domain_narratives: IndexMap<Domain, Narrative>,
+
+    /// Keyed by aspect CID, e.g. Business.1
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    #[serde(default = "IndexMap::new")]
+    aspect_narratives: IndexMap<CID, Narrative>,
+
+    #[serde(skip_serializing_if = "Policy::is_empty")]
+    #[serde(default)]
+    policy: Policy,
+
     #[serde(flatten)]
     controls: IndexMap<CID, Control>,
 }
@@ -34,6 +63,11 @@ impl SOCData {
         SOCData {
             controls: indexmap,
             notes: None,
+            out_of_scope: Vec::new(),
+            domain_narratives: IndexMap::new(),
+            aspect_narratives: IndexMap::new(),
+            policy: Policy::default(),
+            finalized: false,
             profile: IndexMap::new(),
         }
     }
@@ -47,6 +81,11 @@ impl SOCData {
         SOCData {
             controls,
             notes,
+            out_of_scope: Vec::new(),
+            domain_narratives: IndexMap::new(),
+            aspect_narratives: IndexMap::new(),
+            policy: Policy::default(),
+            finalized: false,
             profile,
         }
     }
@@ -73,6 +112,42 @@ impl SOCData {
             .filter(move |(cid, _control)| cid.domain().eq(domain))
     }
 
+    pub fn controls_by_tag<'a>(
+        &'a self,
+        tag: &'a str,
+    ) -> impl Iterator<Item = (&'a CID, &'a Control)> {
+        self.controls
+            .iter()
+            .filter(move |(_cid, control)| control.has_tag(tag))
+    }
+
+    /// All tags in use, sorted and without duplicates
+    pub fn tags(&self) -> Vec<&String> {
+        self.controls
+            .values()
+            .flat_map(|control| control.tags())
+            .unique()
+            .sorted()
+            .collect()
+    }
+
+    /// Improvement actions of all controls, sorted by due date and then by domain and CID.
+    /// Actions without a due date come last.
+    pub fn action_plan(&self) -> Vec<(&CID, &Action)> {
+        self.controls
+            .iter()
+            .filter_map(|(cid, control)| control.action().map(|action| (cid, action)))
+            .sorted_by(|(cid_a, action_a), (cid_b, action_b)| {
+                action_a
+                    .due()
+                    .is_none()
+                    .cmp(&action_b.due().is_none())
+                    .then(action_a.due().cmp(&action_b.due()))
+                    .then(cid_a.cmp(cid_b))
+            })
+            .collect()
+    }
+
     /// Should not be public because user should modify controls over set_x(cid)
     fn control_mut(&mut self, cid: &CID) -> Option<&mut Control> {
         self.controls.get_mut(cid)
@@ -94,12 +169,14 @@ impl SOCData {
             })
             .map(|(_, ctrl)| ctrl)
             .collect();
+        if children.is_empty() {
+            return Score::not_applicable(0.0);
+        }
         Score::new(
             children
                 .iter()
                 .filter(|ctrl| ctrl.answer().eq(&Answer::Bool(true)))
-                .collect::<Vec<_>>()
-                .len() as f64,
+                .count() as f64,
             children.len() as f64,
         )
     }
@@ -116,12 +193,70 @@ impl SOCData {
         }
     }
 
+    pub fn add_comment(&mut self, cid: &CID, comment: Comment) {
+        if let Some(control) = self.control_mut(cid) {
+            control.add_comment(comment);
+        }
+    }
+
+    pub fn remove_comment(&mut self, cid: &CID, index: usize) {
+        if let Some(control) = self.control_mut(cid) {
+            control.remove_comment(index);
+        }
+    }
+
+    pub fn toggle_comment_resolved(&mut self, cid: &CID, index: usize) {
+        if let Some(control) = self.control_mut(cid) {
+            control.toggle_comment_resolved(index);
+        }
+    }
+
+    pub fn add_tag(&mut self, cid: &CID, tag: &str) {
+        if let Some(control) = self.control_mut(cid) {
+            control.add_tag(tag);
+        }
+    }
+
+    pub fn remove_tag(&mut self, cid: &CID, tag: &str) {
+        if let Some(control) = self.control_mut(cid) {
+            control.remove_tag(tag);
+        }
+    }
+
+    pub fn set_action(&mut self, cid: &CID, action: Option<Action>) {
+        if let Some(control) = self.control_mut(cid) {
+            control.set_action(action);
+        }
+    }
+
     pub fn toggle_bookmark(&mut self, cid: &CID) {
         if let Some(control) = self.control_mut(cid) {
             control.toggle_bookmark();
         }
     }
 
+    /// A control is out of scope if itself or one of its parents (section, aspect) is out of scope
+    pub fn in_scope(&self, cid: &CID) -> bool {
+        !self
+            .out_of_scope
+            .iter()
+            .any(|scope| scope == cid || cid.is_child_of(scope))
+    }
+
+    pub fn out_of_scope(&self) -> &Vec<CID> {
+        &self.out_of_scope
+    }
+
+    /// Only toggles the given aspect or section, a section within an out of scope aspect stays out of scope
+    pub fn toggle_scope(&mut self, cid: &CID) {
+        if let Some(index) = self.out_of_scope.iter().position(|scope| scope == cid) {
+            self.out_of_scope.remove(index);
+        } else {
+            self.out_of_scope.push(*cid);
+            self.out_of_scope.sort();
+        }
+    }
+
     pub fn notes(&self) -> Option<&String> {
         self.notes.as_ref()
     }
@@ -138,6 +273,105 @@ impl SOCData {
         self.profile.get(&id)
     }
 
+    /// Controls answered with not applicable but without a justification comment
+    pub fn missing_justifications(&self) -> impl Iterator<Item = &CID> {
+        self.controls
+            .iter()
+            .filter(|(_cid, control)| control.missing_justification())
+            .map(|(cid, _control)| cid)
+    }
+
+    pub fn domain_narrative(&self, domain: &Domain) -> Option<&Narrative> {
+        self.domain_narratives.get(domain)
+    }
+
+    pub fn set_domain_narrative(
+        &mut self,
+        domain: &Domain,
+        section: NarrativeSection,
+        markdown: Option<String>,
+    ) {
+        let narrative = self.domain_narratives.entry(*domain).or_default();
+        narrative.set(section, markdown);
+        if narrative.is_empty() {
+            self.domain_narratives.shift_remove(domain);
+        } else {
+            self.domain_narratives.sort_keys();
+        }
+    }
+
+    pub fn aspect_narrative(&self, aspect: &CID) -> Option<&Narrative> {
+        self.aspect_narratives.get(aspect)
+    }
+
+    pub fn set_aspect_narrative(
+        &mut self,
+        aspect: &CID,
+        section: NarrativeSection,
+        markdown: Option<String>,
+    ) {
+        let narrative = self.aspect_narratives.entry(*aspect).or_default();
+        narrative.set(section, markdown);
+        if narrative.is_empty() {
+            self.aspect_narratives.shift_remove(aspect);
+        } else {
+            self.aspect_narratives.sort_keys();
+        }
+    }
+
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: Policy) {
+        self.policy = policy;
+    }
+
+    pub fn add_evidence(&mut self, cid: &CID, reference: &str) {
+        if let Some(control) = self.control_mut(cid) {
+            control.add_evidence(reference);
+        }
+    }
+
+    pub fn remove_evidence(&mut self, cid: &CID, reference: &str) {
+        if let Some(control) = self.control_mut(cid) {
+            control.remove_evidence(reference);
+        }
+    }
+
+    /// Policy violations of all controls in scope, sorted by CID
+    pub fn violations(&self) -> Vec<Violation> {
+        self.controls
+            .iter()
+            .filter(|(cid, _control)| self.in_scope(cid))
+            .flat_map(|(cid, control)| self.policy.check(cid, control))
+            .collect()
+    }
+
+    /// Edits after finalizing can violate the policy again, which reopens the assessment
+    pub fn finalized(&self) -> bool {
+        self.finalized && self.violations().is_empty()
+    }
+
+    /// Only possible while there are no policy violations and every not applicable answer
+    /// is justified
+    pub fn finalize(&mut self) -> crate::Result<()> {
+        let violations = self.violations().len();
+        if violations > 0 {
+            return Err(CmmError::PolicyViolations(violations));
+        }
+        let missing = self.missing_justifications().count();
+        if missing > 0 {
+            return Err(CmmError::MissingJustifications(missing));
+        }
+        self.finalized = true;
+        Ok(())
+    }
+
+    pub fn reopen(&mut self) {
+        self.finalized = false;
+    }
+
     pub fn has_pinned_items(&self) -> bool {
         self.controls
             .iter()
@@ -158,6 +392,11 @@ impl From<&Schema> for SOCData {
                     .sorted_by_key(|(cid,_control)|*cid)
                     .collect(),
             notes: None,
+            out_of_scope: Vec::new(),
+            domain_narratives: IndexMap::new(),
+            aspect_narratives: IndexMap::new(),
+            policy: Policy::default(),
+            finalized: false,
             profile: IndexMap::new(),
         }
     }
@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use strum::VariantArray;
+
+use crate::{
+    cid::{CID, Domain},
+    control::Control,
+    data::SOCData,
+};
+
+/// Part of a control which differs between two assessments
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, strum::Display)]
+pub enum ChangedField {
+    /// The control only exists in one of the assessments, the values are its answers
+    Control,
+    Answer,
+    Comment,
+    Tags,
+    Evidence,
+}
+
+/// Difference of a single field of a control, None if the value is not set
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Change {
+    cid: CID,
+    field: ChangedField,
+    before: Option<String>,
+    after: Option<String>,
+}
+
+impl Change {
+    pub fn cid(&self) -> &CID {
+        &self.cid
+    }
+
+    pub fn field(&self) -> ChangedField {
+        self.field
+    }
+
+    pub fn before(&self) -> Option<&String> {
+        self.before.as_ref()
+    }
+
+    pub fn after(&self) -> Option<&String> {
+        self.after.as_ref()
+    }
+}
+
+impl SOCData {
+    /// Changes of the controls from `self` to `other`, ordered by CID
+    pub fn diff(&self, other: &SOCData) -> Vec<Change> {
+        let mut cids: Vec<&CID> = Domain::VARIANTS
+            .iter()
+            .flat_map(|domain| {
+                self.controls_by_domain(domain)
+                    .chain(other.controls_by_domain(domain))
+            })
+            .map(|(cid, _control)| cid)
+            .collect();
+        cids.sort();
+        cids.dedup();
+
+        cids.into_iter()
+            .flat_map(|cid| match (self.control(cid), other.control(cid)) {
+                (Some(before), Some(after)) => changes(cid, before, after),
+                (before, after) => Vec::from([Change {
+                    cid: *cid,
+                    field: ChangedField::Control,
+                    before: before.map(|control| control.answer().to_string()),
+                    after: after.map(|control| control.answer().to_string()),
+                }]),
+            })
+            .collect()
+    }
+}
+
+fn changes(cid: &CID, before: &Control, after: &Control) -> Vec<Change> {
+    let list = |values: &Vec<String>| (!values.is_empty()).then(|| values.join(", "));
+    [
+        (
+            ChangedField::Answer,
+            Some(before.answer().to_string()),
+            Some(after.answer().to_string()),
+        ),
+        (
+            ChangedField::Comment,
+            before.comment().clone(),
+            after.comment().clone(),
+        ),
+        (ChangedField::Tags, list(before.tags()), list(after.tags())),
+        (
+            ChangedField::Evidence,
+            list(before.evidence()),
+            list(after.evidence()),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_field, before, after)| before != after)
+    .map(|(field, before, after)| Change {
+        cid: *cid,
+        field,
+        before,
+        after,
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use crate::answer::{Answer, Detailed};
+
+    use super::*;
+
+    fn data(controls: Vec<(&str, Control)>) -> SOCData {
+        let controls = controls
+            .into_iter()
+            .map(|(cid, control)| (cid.parse().unwrap(), control))
+            .collect();
+        SOCData::new(controls, None, IndexMap::new())
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = data(vec![
+            (
+                "Business.1.1",
+                Control::new(Answer::Detailed(Detailed::Partially), None),
+            ),
+            (
+                "Business.1.2",
+                Control::new(Answer::Detailed(Detailed::No), None),
+            ),
+        ]);
+        let mut after = data(vec![
+            (
+                "Business.1.1",
+                Control::new(
+                    Answer::Detailed(Detailed::Fully),
+                    Some(String::from("Charter signed")),
+                ),
+            ),
+            (
+                "Business.1.3",
+                Control::new(Answer::Detailed(Detailed::No), None),
+            ),
+        ]);
+        after.add_tag(&"Business.1.1".parse().unwrap(), "quick-win");
+
+        let changes = before.diff(&after);
+        let fields: Vec<(String, ChangedField)> = changes
+            .iter()
+            .map(|change| (change.cid().to_string(), change.field()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (String::from("Business.1.1"), ChangedField::Answer),
+                (String::from("Business.1.1"), ChangedField::Comment),
+                (String::from("Business.1.1"), ChangedField::Tags),
+                (String::from("Business.1.2"), ChangedField::Control),
+                (String::from("Business.1.3"), ChangedField::Control),
+            ]
+        );
+        assert_eq!(changes[0].before().map(String::as_str), Some("Partially"));
+        assert_eq!(changes[0].after().map(String::as_str), Some("Fully"));
+        assert_eq!(changes[3].after(), None);
+        assert_eq!(changes[4].before(), None);
+        assert!(before.diff(&before).is_empty());
+    }
+}
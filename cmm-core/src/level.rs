@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use strum::{FromRepr, VariantArray};
+
+use crate::{CmmError, score::Score};
+
+/// SOC-CMM maturity levels for aspect, domain and overall scores
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Copy,
+    FromRepr,
+    VariantArray,
+    Serialize,
+    Deserialize,
+    strum::Display,
+)]
+pub enum MaturityLevel {
+    #[strum(to_string = "Non-existent")]
+    NonExistent = 0,
+    Initial = 1,
+    Managed = 2,
+    Defined = 3,
+    #[strum(to_string = "Quantitatively managed")]
+    QuantitativelyManaged = 4,
+    Optimizing = 5,
+}
+
+/// SOC-CMM capability levels for technology and service capability scores
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Clone,
+    Copy,
+    FromRepr,
+    VariantArray,
+    Serialize,
+    Deserialize,
+    strum::Display,
+)]
+pub enum CapabilityLevel {
+    Incomplete = 0,
+    Performed = 1,
+    Managed = 2,
+    Defined = 3,
+}
+
+/// Minimum normalized score to reach a level.
+/// Maturity scores are normalized to 0-5, capability scores to 0-3.
+/// The overall level is taken from `Stats::maturity_overall`, not from the overall score.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedThresholds")]
+pub struct LevelThresholds {
+    /// Thresholds for the levels 1 to 5
+    maturity: [f64; 5],
+    /// Thresholds for the levels 1 to 3
+    capability: [f64; 3],
+}
+
+/// Deserialized thresholds before the check of `LevelThresholds::new`
+#[derive(Deserialize)]
+struct UncheckedThresholds {
+    maturity: [f64; 5],
+    capability: [f64; 3],
+}
+
+impl TryFrom<UncheckedThresholds> for LevelThresholds {
+    type Error = CmmError;
+
+    fn try_from(thresholds: UncheckedThresholds) -> Result<Self, Self::Error> {
+        Self::new(thresholds.maturity, thresholds.capability)
+    }
+}
+
+impl Default for LevelThresholds {
+    fn default() -> Self {
+        Self {
+            maturity: [1.0, 2.0, 3.0, 4.0, 5.0],
+            capability: [1.0, 2.0, 3.0],
+        }
+    }
+}
+
+impl LevelThresholds {
+    /// Thresholds have to be ascending
+    pub fn new(maturity: [f64; 5], capability: [f64; 3]) -> crate::Result<Self> {
+        if !maturity.is_sorted() || !capability.is_sorted() {
+            return Err(CmmError::UnsortedThresholds);
+        }
+        Ok(Self {
+            maturity,
+            capability,
+        })
+    }
+
+    pub fn maturity(&self) -> &[f64; 5] {
+        &self.maturity
+    }
+
+    pub fn capability(&self) -> &[f64; 3] {
+        &self.capability
+    }
+
+    /// None if the score is not applicable
+    pub fn maturity_level(&self, score: &Score) -> Option<MaturityLevel> {
+        let normalized = score.score()? / score.max() * 5.0;
+        let level = reached(&self.maturity, normalized);
+        MaturityLevel::from_repr(level)
+    }
+
+    /// None if the score is not applicable
+    pub fn capability_level(&self, score: &Score) -> Option<CapabilityLevel> {
+        let normalized = score.score()? / score.max() * 3.0;
+        let level = reached(&self.capability, normalized);
+        CapabilityLevel::from_repr(level)
+    }
+}
+
+fn reached(thresholds: &[f64], normalized: f64) -> usize {
+    thresholds
+        .iter()
+        .take_while(|threshold| normalized >= **threshold)
+        .count()
+}
+
+impl MaturityLevel {
+    pub fn level(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl CapabilityLevel {
+    pub fn level(&self) -> u8 {
+        *self as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maturity_level() {
+        let thresholds = LevelThresholds::default();
+        assert_eq!(
+            thresholds.maturity_level(&Score::new(2.34, 5.0)),
+            Some(MaturityLevel::Managed)
+        );
+        assert_eq!(
+            thresholds.maturity_level(&Score::new(0.0, 5.0)),
+            Some(MaturityLevel::NonExistent)
+        );
+        assert_eq!(
+            thresholds.maturity_level(&Score::new(5.0, 5.0)),
+            Some(MaturityLevel::Optimizing)
+        );
+        assert_eq!(thresholds.maturity_level(&Score::not_applicable(5.0)), None);
+        assert_eq!(MaturityLevel::QuantitativelyManaged.to_string(), "Quantitatively managed");
+    }
+
+    #[test]
+    fn test_capability_level() {
+        let thresholds = LevelThresholds::default();
+        assert_eq!(
+            thresholds.capability_level(&Score::new(1.5, 3.0)),
+            Some(CapabilityLevel::Performed)
+        );
+        assert_eq!(
+            thresholds.capability_level(&Score::new(3.0, 3.0)),
+            Some(CapabilityLevel::Defined)
+        );
+    }
+
+    #[test]
+    fn test_custom_thresholds() {
+        let thresholds =
+            LevelThresholds::new([0.5, 1.5, 2.5, 3.5, 4.5], [0.5, 1.5, 2.5]).unwrap();
+        assert_eq!(
+            thresholds.maturity_level(&Score::new(2.5, 5.0)),
+            Some(MaturityLevel::Defined)
+        );
+        assert!(LevelThresholds::new([2.0, 1.0, 3.0, 4.0, 5.0], [1.0, 2.0, 3.0]).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_thresholds() {
+        let thresholds: LevelThresholds = serde_json::from_str(
+            r#"{"maturity":[0.5,1.5,2.5,3.5,4.5],"capability":[0.5,1.5,2.5]}"#,
+        )
+        .unwrap();
+        assert_eq!(thresholds.maturity(), &[0.5, 1.5, 2.5, 3.5, 4.5]);
+        assert!(
+            serde_json::from_str::<LevelThresholds>(
+                r#"{"maturity":[5.0,4.0,3.0,2.0,1.0],"capability":[1.0,2.0,3.0]}"#
+            )
+            .is_err()
+        );
+    }
+}
@@ -3,13 +3,22 @@ use std::str::ParseBoolError;
 
 use answer::Answer;
 
+pub mod action;
 pub mod answer;
 pub mod cid;
 pub mod control;
 pub mod data;
+pub mod diff;
+pub mod level;
+pub mod lint;
+pub mod narrative;
+pub mod policy;
 pub mod schema;
 pub mod score;
+pub mod scoring;
+pub mod search;
 pub mod profile;
+pub mod report;
 
 use thiserror::Error;
 
@@ -30,6 +39,14 @@ pub enum CmmError {
     CIDInvalidZero,
     #[error("CID parsing error: Identifier is malformed {0}")]
     CIDMalformed(#[from] ParseIntError),
+    #[error("{0} is not a valid date, expected yyyy-mm-dd")]
+    InvalidDate(String),
+    #[error("Level thresholds have to be ascending")]
+    UnsortedThresholds,
+    #[error("Cannot finalize the assessment, {0} policy violation(s) remain")]
+    PolicyViolations(usize),
+    #[error("Cannot finalize the assessment, {0} not applicable answer(s) lack a justification")]
+    MissingJustifications(usize),
     #[error(transparent)]
     StrumParseError(#[from] strum::ParseError),
     #[error(transparent)]
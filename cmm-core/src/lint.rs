@@ -0,0 +1,355 @@
+//! Consistency rules which flag suspicious answer combinations.
+//! The rules are defined per soc-cmm version next to the schema, e.g. `lint-2.3.4.json`:
+//! {
+//!     "rules": [
+//!         { "rule": "Contradiction", "control": "Process.1.2", "at_least": 4, "parent": "Process.1.1" },
+//!         { "rule": "ExceedsCompleteness", "control": "Business.3.1", "section": "Business.3.2", "max_gap": 0.5 },
+//!         { "rule": "NotDeployed", "control": "Technology.1.7.26", "at_least": 4, "checklist": "Technology.4.7" }
+//!     ]
+//! }
+//!
+//! 2.3.4 has no Bool checklist telling whether a tool is deployed, `NotDeployed` rules use the
+//! capabilities of the tool instead, e.g. `Technology.4.7` for SOAR. These contain integrations
+//! with other tools, so the controls of the other `NotDeployed` rules are not counted as deployed
+//! capabilities, otherwise two integrations could satisfy each other.
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answer::{Answer, DetailedOptional},
+    cid::CID,
+    data::SOCData,
+};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Lint {
+    rules: Vec<LintRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(tag = "rule")]
+pub enum LintRule {
+    /// `control` is answered at level `at_least` or higher while `parent` is answered with No / Never / false
+    Contradiction {
+        control: CID,
+        at_least: u32,
+        parent: CID,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+    /// The maturity of `control` is more than `max_gap` (0-1) above the completeness of `section`
+    ExceedsCompleteness {
+        control: CID,
+        section: CID,
+        max_gap: f64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+    /// `control` is answered at level `at_least` or higher while every item of the tool's
+    /// `checklist` is answered with No / false, i.e. the tool is not deployed.
+    /// Not required and not applicable items are skipped.
+    NotDeployed {
+        control: CID,
+        at_least: u32,
+        checklist: CID,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+}
+
+/// A rule which was violated by the answer of `cid`
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct Finding {
+    cid: CID,
+    related: CID,
+    message: String,
+}
+
+impl Finding {
+    pub fn cid(&self) -> &CID {
+        &self.cid
+    }
+
+    /// The control or section which contradicts the answer of `cid`
+    pub fn related(&self) -> &CID {
+        &self.related
+    }
+
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+}
+
+impl Lint {
+    pub fn new(rules: Vec<LintRule>) -> Self {
+        Self { rules }
+    }
+
+    pub fn rules(&self) -> &Vec<LintRule> {
+        &self.rules
+    }
+
+    /// Findings of all rules, sorted by CID. Rules referencing unknown controls are skipped.
+    pub fn check(&self, data: &SOCData) -> Vec<Finding> {
+        let integrations: Vec<CID> = self
+            .rules
+            .iter()
+            .filter_map(|rule| match rule {
+                LintRule::NotDeployed { control, .. } => Some(*control),
+                _ => None,
+            })
+            .collect();
+        let mut findings: Vec<Finding> = self
+            .rules
+            .iter()
+            .filter_map(|rule| rule.check_with(data, &integrations))
+            .collect();
+        findings.sort_by_key(|finding| finding.cid);
+        findings
+    }
+
+    pub fn findings_for<'a>(
+        findings: &'a [Finding],
+        cid: &'a CID,
+    ) -> impl Iterator<Item = &'a Finding> {
+        findings.iter().filter(move |finding| finding.cid == *cid)
+    }
+}
+
+impl LintRule {
+    pub fn check(&self, data: &SOCData) -> Option<Finding> {
+        self.check_with(data, &[])
+    }
+
+    /// `integrations` are skipped in the checklists of `NotDeployed`
+    fn check_with(&self, data: &SOCData, integrations: &[CID]) -> Option<Finding> {
+        match self {
+            LintRule::Contradiction {
+                control,
+                at_least,
+                parent,
+                message,
+            } => {
+                let level = data.control(control)?.answer().level()?;
+                let parent_answer = data.control(parent)?.answer();
+                (level >= *at_least && parent_answer.is_negative()).then(|| Finding {
+                    cid: *control,
+                    related: *parent,
+                    message: message.clone().unwrap_or(format!(
+                        "Answered with level {level} while {parent} is answered with {parent_answer}"
+                    )),
+                })
+            }
+            LintRule::ExceedsCompleteness {
+                control,
+                section,
+                max_gap,
+                message,
+            } => {
+                let level = data.control(control)?.answer().level()?;
+                let completeness = data.section_completeness(section).as_percentage()? / 100.0;
+                let maturity = (level - 1) as f64 / 4.0;
+                (maturity - completeness > *max_gap).then(|| Finding {
+                    cid: *control,
+                    related: *section,
+                    message: message.clone().unwrap_or(format!(
+                        "Answered with level {level} while only {:.0}% of {section} is complete",
+                        completeness * 100.0
+                    )),
+                })
+            }
+            LintRule::NotDeployed {
+                control,
+                at_least,
+                checklist,
+                message,
+            } => {
+                let level = data.control(control)?.answer().level()?;
+                let domain = checklist.domain();
+                let mut items = data
+                    .controls_by_domain(&domain)
+                    .filter(|(cid, item)| {
+                        cid.is_child_of(checklist)
+                            && !integrations.contains(cid)
+                            && !item.answer().is_not_applicable()
+                            && *item.answer()
+                                != Answer::DetailedOptional(DetailedOptional::NotRequired)
+                    })
+                    .peekable();
+                items.peek()?;
+                let deployed = items.any(|(_cid, item)| !item.answer().is_negative());
+                (level >= *at_least && !deployed).then(|| Finding {
+                    cid: *control,
+                    related: *checklist,
+                    message: message.clone().unwrap_or(format!(
+                        "Answered with level {level} while nothing of {checklist} is in place"
+                    )),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use crate::{answer::Detailed, control::Control};
+
+    use super::*;
+
+    fn data(controls: Vec<(&str, Answer)>) -> SOCData {
+        SOCData::new(
+            controls
+                .into_iter()
+                .map(|(cid, answer)| (cid.parse().unwrap(), Control::new(answer, None)))
+                .collect(),
+            None,
+            IndexMap::new(),
+        )
+    }
+
+    #[test]
+    fn test_contradiction() {
+        let lint: Lint = serde_json::from_str(
+            r#"{"rules": [{"rule": "Contradiction", "control": "Process.1.2", "at_least": 4, "parent": "Process.1.1"}]}"#,
+        )
+        .unwrap();
+        let cid: CID = "Process.1.2".parse().unwrap();
+
+        let findings = lint.check(&data(vec![
+            ("Process.1.1", Answer::Detailed(Detailed::No)),
+            ("Process.1.2", Answer::Detailed(Detailed::Fully)),
+        ]));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].cid(), &cid);
+        assert_eq!(Lint::findings_for(&findings, &cid).count(), 1);
+
+        let findings = lint.check(&data(vec![
+            ("Process.1.1", Answer::Detailed(Detailed::No)),
+            ("Process.1.2", Answer::Detailed(Detailed::Averagely)),
+        ]));
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_contradiction_bool_parent() {
+        let lint = Lint::new(vec![LintRule::Contradiction {
+            control: "Technology.1.2".parse().unwrap(),
+            at_least: 5,
+            parent: "Technology.1.1".parse().unwrap(),
+            message: Some(String::from("Tool is not deployed")),
+        }]);
+
+        let findings = lint.check(&data(vec![
+            ("Technology.1.1", Answer::Bool(false)),
+            (
+                "Technology.1.2",
+                Answer::DetailedOptional(DetailedOptional::Fully),
+            ),
+        ]));
+        assert_eq!(findings[0].message(), "Tool is not deployed");
+
+        let findings = lint.check(&data(vec![
+            ("Technology.1.1", Answer::Bool(false)),
+            (
+                "Technology.1.2",
+                Answer::DetailedOptional(DetailedOptional::NotRequired),
+            ),
+        ]));
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn test_exceeds_completeness() {
+        let lint = Lint::new(vec![LintRule::ExceedsCompleteness {
+            control: "Business.3.1".parse().unwrap(),
+            section: "Business.3.2".parse().unwrap(),
+            max_gap: 0.5,
+            message: None,
+        }]);
+        let mut controls = vec![
+            ("Business.3.1", Answer::Detailed(Detailed::Fully)),
+            ("Business.3.2.1", Answer::Bool(true)),
+            ("Business.3.2.2", Answer::Bool(false)),
+            ("Business.3.2.3", Answer::Bool(false)),
+            ("Business.3.2.4", Answer::Bool(false)),
+        ];
+
+        let findings = lint.check(&data(controls.clone()));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].related(), &"Business.3.2".parse().unwrap());
+
+        controls[2].1 = Answer::Bool(true);
+        assert!(lint.check(&data(controls)).is_empty());
+    }
+
+    #[test]
+    fn test_not_deployed() {
+        let lint = Lint::new(vec![LintRule::NotDeployed {
+            control: "Technology.1.7.26".parse().unwrap(),
+            at_least: 4,
+            checklist: "Technology.4.7".parse().unwrap(),
+            message: None,
+        }]);
+        let mut controls = vec![
+            (
+                "Technology.1.7.26",
+                Answer::DetailedOptional(DetailedOptional::Fully),
+            ),
+            ("Technology.4.7.1", Answer::Bool(false)),
+            (
+                "Technology.4.7.2",
+                Answer::DetailedOptional(DetailedOptional::No),
+            ),
+        ];
+
+        let findings = lint.check(&data(controls.clone()));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].message(),
+            "Answered with level 5 while nothing of Technology.4.7 is in place"
+        );
+
+        controls[1].1 = Answer::Bool(true);
+        assert!(lint.check(&data(controls.clone())).is_empty());
+
+        // Without a checklist nothing is known about the deployment
+        assert!(lint.check(&data(controls[..1].to_vec())).is_empty());
+
+        // Not required capabilities are no deployment
+        controls[1].1 = Answer::Bool(false);
+        controls[2].1 = Answer::DetailedOptional(DetailedOptional::NotRequired);
+        assert_eq!(lint.check(&data(controls.clone())).len(), 1);
+    }
+
+    #[test]
+    fn test_not_deployed_integrations() {
+        let rule = |control: &str, checklist: &str| LintRule::NotDeployed {
+            control: control.parse().unwrap(),
+            at_least: 4,
+            checklist: checklist.parse().unwrap(),
+            message: None,
+        };
+        let lint = Lint::new(vec![
+            rule("Technology.1.7.26", "Technology.4.7"),
+            rule("Technology.4.7.4", "Technology.1.7"),
+        ]);
+        let fully = Answer::DetailedOptional(DetailedOptional::Fully);
+        let no = Answer::DetailedOptional(DetailedOptional::No);
+        let controls = vec![
+            ("Technology.1.7.1", no.clone()),
+            ("Technology.1.7.26", fully.clone()),
+            ("Technology.4.7.1", no),
+            ("Technology.4.7.4", fully),
+        ];
+        // The integrations alone don't count as deployed SIEM or SOAR
+        let findings = lint.check(&data(controls));
+        assert_eq!(
+            findings
+                .iter()
+                .map(|finding| finding.cid().to_string())
+                .collect::<Vec<_>>(),
+            vec!["Technology.1.7.26", "Technology.4.7.4"]
+        );
+    }
+}
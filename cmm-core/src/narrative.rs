@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use strum::VariantArray;
+
+#[derive(
+    Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, VariantArray, strum::Display,
+)]
+pub enum NarrativeSection {
+    Findings,
+    Strengths,
+    Recommendations,
+}
+
+/// Assessor markdown for a domain or an aspect, printed next to its scores in the report
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+pub struct Narrative {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    findings: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strengths: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recommendations: Option<String>,
+}
+
+impl Narrative {
+    pub fn get(&self, section: NarrativeSection) -> Option<&String> {
+        match section {
+            NarrativeSection::Findings => self.findings.as_ref(),
+            NarrativeSection::Strengths => self.strengths.as_ref(),
+            NarrativeSection::Recommendations => self.recommendations.as_ref(),
+        }
+    }
+
+    /// Blank markdown is stored as None
+    pub fn set(&mut self, section: NarrativeSection, markdown: Option<String>) {
+        let markdown = markdown.filter(|markdown| !markdown.trim().is_empty());
+        match section {
+            NarrativeSection::Findings => self.findings = markdown,
+            NarrativeSection::Strengths => self.strengths = markdown,
+            NarrativeSection::Recommendations => self.recommendations = markdown,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        NarrativeSection::VARIANTS
+            .iter()
+            .all(|section| self.get(*section).is_none())
+    }
+}
@@ -0,0 +1,190 @@
+use std::ops::Not;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{cid::CID, control::Control};
+
+/// Requirements which make high answers defensible, e.g. "level 4 or above requires a comment"
+/// or "Fully requires at least one evidence reference". An assessment can only be finalized
+/// once there are no violations left.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+pub struct Policy {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    rules: Vec<PolicyRule>,
+}
+
+/// Applies to all scored answers at level `at_least` (1-5) or higher
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct PolicyRule {
+    at_least: u32,
+
+    /// A comment or an entry in the discussion thread is required
+    #[serde(skip_serializing_if = "<&bool>::not")]
+    #[serde(default)]
+    comment: bool,
+
+    /// Minimum number of evidence references
+    #[serde(skip_serializing_if = "is_zero")]
+    #[serde(default)]
+    evidence: usize,
+}
+
+/// A policy rule which is not satisfied by the control `cid`
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct Violation {
+    cid: CID,
+    message: String,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+impl Policy {
+    pub fn new(rules: Vec<PolicyRule>) -> Self {
+        Self { rules }
+    }
+
+    pub fn rules(&self) -> &Vec<PolicyRule> {
+        &self.rules
+    }
+
+    pub fn add_rule(&mut self, rule: PolicyRule) {
+        self.rules.push(rule);
+    }
+
+    pub fn remove_rule(&mut self, index: usize) {
+        if index < self.rules.len() {
+            self.rules.remove(index);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Violations of a single control, one per unsatisfied rule
+    pub fn check(&self, cid: &CID, control: &Control) -> Vec<Violation> {
+        self.rules
+            .iter()
+            .filter_map(|rule| {
+                rule.check(control)
+                    .map(|message| Violation { cid: *cid, message })
+            })
+            .collect()
+    }
+}
+
+impl PolicyRule {
+    pub fn new(at_least: u32, comment: bool, evidence: usize) -> Self {
+        Self {
+            at_least,
+            comment,
+            evidence,
+        }
+    }
+
+    pub fn at_least(&self) -> u32 {
+        self.at_least
+    }
+
+    pub fn comment(&self) -> bool {
+        self.comment
+    }
+
+    pub fn evidence(&self) -> usize {
+        self.evidence
+    }
+
+    /// The reason why the control does not satisfy this rule
+    pub fn check(&self, control: &Control) -> Option<String> {
+        let level = control.answer().level()?;
+        if level < self.at_least {
+            return None;
+        }
+        if self.comment && !control.has_comment() {
+            return Some(format!("Level {level} requires a comment"));
+        }
+        if control.evidence().len() < self.evidence {
+            return Some(format!(
+                "Level {level} requires at least {} evidence reference(s)",
+                self.evidence
+            ));
+        }
+        None
+    }
+}
+
+impl Violation {
+    pub fn cid(&self) -> &CID {
+        &self.cid
+    }
+
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::{Answer, Detailed, DetailedOptional};
+
+    use super::*;
+
+    #[test]
+    fn test_policy_rule() {
+        let policy = Policy::new(vec![
+            PolicyRule::new(4, true, 0),
+            PolicyRule::new(5, false, 1),
+        ]);
+        let cid: CID = "Business.1.1".parse().unwrap();
+
+        let mut control = Control::new(Answer::Detailed(Detailed::Fully), None);
+        assert_eq!(policy.check(&cid, &control).len(), 2);
+
+        control.set_comment(Some(String::from("Seen the charter")));
+        assert_eq!(policy.check(&cid, &control).len(), 1);
+
+        control.add_evidence("CHARTER-v1.pdf");
+        assert!(policy.check(&cid, &control).is_empty());
+
+        let control = Control::new(Answer::Detailed(Detailed::Averagely), None);
+        assert!(policy.check(&cid, &control).is_empty());
+    }
+
+    #[test]
+    fn test_policy_ignores_unscored() {
+        let policy = Policy::new(vec![PolicyRule::new(1, true, 0)]);
+        let cid: CID = "Business.1.1".parse().unwrap();
+
+        let control = Control::new(
+            Answer::DetailedOptional(DetailedOptional::NotRequired),
+            None,
+        );
+        assert!(policy.check(&cid, &control).is_empty());
+
+        let control = Control::new(Answer::Bool(true), None);
+        assert!(policy.check(&cid, &control).is_empty());
+    }
+
+    #[test]
+    fn test_policy_toml() {
+        let policy: Policy = toml::from_str(
+            r#"
+            rules = [
+                { at_least = 4, comment = true },
+                { at_least = 5, evidence = 1 },
+            ]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            policy,
+            Policy::new(vec![
+                PolicyRule::new(4, true, 0),
+                PolicyRule::new(5, false, 1)
+            ])
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use strum::VariantArray;
+
+use crate::{
+    cid::Domain,
+    level::LevelThresholds,
+    score::{Score, Stats},
+};
+
+/// All scores of an assessment with their levels, e.g. to feed dashboards.
+/// Not applicable scores are serialized as `null`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ScoreReport {
+    /// Average maturity of the domains (0-5) and its level
+    overall: ScoreEntry,
+    /// Average capability of Technology and Services (0-3) and its level
+    overall_capability: ScoreEntry,
+    domains: Vec<DomainScores>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct DomainScores {
+    domain: Domain,
+    maturity: ScoreEntry,
+    capability: ScoreEntry,
+    aspects: Vec<AspectScores>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AspectScores {
+    /// Starts at 1, like the aspect of a CID
+    id: u8,
+    title: String,
+    maturity: ScoreEntry,
+    capability: ScoreEntry,
+}
+
+/// A score with its percentage and the reached level, e.g. 2 "Managed"
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    score: Option<f64>,
+    max: f64,
+    percentage: Option<f64>,
+    level: Option<u8>,
+    label: Option<String>,
+}
+
+impl ScoreReport {
+    /// Aspects and their titles are taken from the schema of `stats`
+    pub fn new(stats: &Stats, thresholds: &LevelThresholds) -> Self {
+        let maturity = |score: Score| {
+            let level = thresholds.maturity_level(&score);
+            ScoreEntry::new(
+                &score,
+                level.map(|level| level.level()),
+                level.map(|level| level.to_string()),
+            )
+        };
+        let capability = |score: Score| {
+            let level = thresholds.capability_level(&score);
+            ScoreEntry::new(
+                &score,
+                level.map(|level| level.level()),
+                level.map(|level| level.to_string()),
+            )
+        };
+
+        let domains = Domain::VARIANTS
+            .iter()
+            .map(|domain| DomainScores {
+                domain: *domain,
+                maturity: maturity(stats.maturity_by_domain(domain)),
+                capability: capability(stats.capability_by_domain(domain)),
+                aspects: stats
+                    .schema()
+                    .aspects(domain)
+                    .into_iter()
+                    .zip(1..)
+                    .map(|(title, id)| AspectScores {
+                        id,
+                        title: title.clone(),
+                        maturity: maturity(stats.maturity_by_aspect(domain, id)),
+                        capability: capability(stats.capability_by_aspect(domain, id)),
+                    })
+                    .collect(),
+            })
+            .collect();
+        Self {
+            overall: maturity(stats.maturity_overall()),
+            overall_capability: capability(stats.capability_overall()),
+            domains,
+        }
+    }
+
+    pub fn overall(&self) -> &ScoreEntry {
+        &self.overall
+    }
+
+    pub fn overall_capability(&self) -> &ScoreEntry {
+        &self.overall_capability
+    }
+
+    pub fn domains(&self) -> &Vec<DomainScores> {
+        &self.domains
+    }
+}
+
+impl DomainScores {
+    pub fn domain(&self) -> Domain {
+        self.domain
+    }
+
+    pub fn maturity(&self) -> &ScoreEntry {
+        &self.maturity
+    }
+
+    pub fn capability(&self) -> &ScoreEntry {
+        &self.capability
+    }
+
+    pub fn aspects(&self) -> &Vec<AspectScores> {
+        &self.aspects
+    }
+}
+
+impl AspectScores {
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    pub fn title(&self) -> &String {
+        &self.title
+    }
+
+    pub fn maturity(&self) -> &ScoreEntry {
+        &self.maturity
+    }
+
+    pub fn capability(&self) -> &ScoreEntry {
+        &self.capability
+    }
+}
+
+impl ScoreEntry {
+    fn new(score: &Score, level: Option<u8>, label: Option<String>) -> Self {
+        Self {
+            score: score.score(),
+            max: score.max(),
+            percentage: score.as_percentage(),
+            level,
+            label,
+        }
+    }
+
+    pub fn score(&self) -> Option<f64> {
+        self.score
+    }
+
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    pub fn percentage(&self) -> Option<f64> {
+        self.percentage
+    }
+
+    pub fn level(&self) -> Option<u8> {
+        self.level
+    }
+
+    pub fn label(&self) -> Option<&String> {
+        self.label.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use crate::{
+        answer::{Answer, Detailed},
+        control::Control,
+        data::SOCData,
+        schema::Schema,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_score_report() {
+        let schema: Schema = serde_json::from_str(include_str!("../../scheme-2.3.4.json")).unwrap();
+        let controls = IndexMap::from([(
+            "Business.1.1".parse().unwrap(),
+            Control::new(Answer::Detailed(Detailed::Fully), None),
+        )]);
+        let stats = Stats::new(SOCData::new(controls, None, IndexMap::new()), schema);
+        let report = ScoreReport::new(&stats, &LevelThresholds::default());
+
+        let business = &report.domains()[0];
+        assert_eq!(business.domain(), Domain::Business);
+        assert_eq!(business.aspects().len(), 5);
+
+        let drivers = &business.aspects()[0];
+        assert_eq!(drivers.id(), 1);
+        assert_eq!(drivers.title(), "Business Drivers");
+        assert_eq!(drivers.maturity().score(), Some(5.0));
+        assert_eq!(drivers.maturity().percentage(), Some(100.0));
+        assert_eq!(drivers.maturity().level(), Some(5));
+        assert_eq!(
+            drivers.maturity().label().map(String::as_str),
+            Some("Optimizing")
+        );
+        assert_eq!(drivers.capability().score(), None);
+        assert_eq!(drivers.capability().label(), None);
+        assert_eq!(business.aspects()[1].maturity().score(), None);
+
+        // Only the maturity of Business is applicable, unanswered controls don't count
+        assert_eq!(business.maturity().percentage(), Some(100.0));
+        assert_eq!(business.capability().score(), None);
+        assert_eq!(report.domains()[1].maturity().score(), None);
+        assert_eq!(report.overall().score(), Some(5.0));
+        assert_eq!(report.overall().max(), 5.0);
+        assert_eq!(report.overall().level(), Some(5));
+        assert_eq!(report.overall_capability().score(), None);
+        assert_eq!(report.overall_capability().max(), 3.0);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            json["domains"][0]["aspects"][1]["maturity"]["level"],
+            serde_json::Value::Null
+        );
+    }
+}
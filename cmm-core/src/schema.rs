@@ -20,7 +20,7 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::ops::Not;
 
-use crate::{cid::{Domain, CID}, profile::ProfileQuestion};
+use crate::{answer::Answer, cid::{Domain, CID}, data::SOCData, profile::ProfileQuestion};
 
 /// This is the soc-cmm schema and only contains Meta Information.
 /// Changes will be made only between soc-cmm versions. The whole struct will be loaded at compile time.
@@ -86,6 +86,26 @@ impl Schema {
     pub fn profile(&self) -> &IndexMap<String, ProfileQuestion> {
         &self.profile
     }
+
+    /// Schema with the opt-in `dependencies` added to its controls
+    pub fn with_dependencies(mut self, dependencies: &Dependencies) -> Self {
+        for (cid, dependency) in &dependencies.dependencies {
+            if let Some(control_schema) = self.control_schemas.get_mut(cid) {
+                control_schema.depends_on = Some(dependency.clone());
+            }
+        }
+        self
+    }
+
+    /// Schema with the `recommendations` added to its controls, replacing those of the schema
+    pub fn with_recommendations(mut self, recommendations: &Recommendations) -> Self {
+        for (cid, recommendations) in &recommendations.recommendations {
+            if let Some(control_schema) = self.control_schemas.get_mut(cid) {
+                control_schema.recommendations = recommendations.clone();
+            }
+        }
+        self
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -115,12 +135,21 @@ pub struct ControlSchema {
     #[serde(skip_serializing_if = "Option::is_none")]
     remarks: Option<String>,
 
+    /// How to get from level N (index N-1) to level N+1, at most four entries
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default = "Vec::new")]
+    recommendations: Vec<String>,
+
     title: String,
     control_type: ControlType,
 
     #[serde(skip_serializing_if = "<&bool>::not")]
     #[serde(default)]
     nist_only: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    depends_on: Option<Dependency>,
 }
 
 impl ControlSchema {
@@ -136,6 +165,15 @@ impl ControlSchema {
         &self.remarks
     }
 
+    pub fn recommendations(&self) -> &Vec<String> {
+        &self.recommendations
+    }
+
+    /// Recommendation to move from `level` to the next level
+    pub fn recommendation(&self, level: u32) -> Option<&String> {
+        self.recommendations.get(level.checked_sub(1)? as usize)
+    }
+
     pub fn control_type(&self) -> &ControlType {
         &self.control_type
     }
@@ -143,4 +181,89 @@ impl ControlSchema {
     pub fn nist_only(&self) -> bool {
         self.nist_only
     }
+
+    pub fn depends_on(&self) -> Option<&Dependency> {
+        self.depends_on.as_ref()
+    }
+}
+
+/// A control which only makes sense if its parent is answered positively.
+/// If the parent is answered with No / Never / false the dependent control is
+/// not applicable, or capped at the given level.
+/// An unanswered parent has the same default answer and blocks its dependents as well.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct Dependency {
+    parent: CID,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    cap: Option<u32>,
+}
+
+impl Dependency {
+    pub fn new(parent: CID, cap: Option<u32>) -> Self {
+        Self { parent, cap }
+    }
+
+    pub fn parent(&self) -> &CID {
+        &self.parent
+    }
+
+    pub fn cap(&self) -> Option<u32> {
+        self.cap
+    }
+
+    /// A missing parent never blocks its dependents
+    pub fn is_met(&self, data: &SOCData) -> bool {
+        data.control(&self.parent)
+            .is_none_or(|parent| !parent.answer().is_negative())
+    }
+
+    /// The answer of a dependent control after applying this dependency
+    pub fn resolve(&self, data: &SOCData, answer: &Answer) -> Answer {
+        if self.is_met(data) {
+            return answer.clone();
+        }
+        match self.cap {
+            Some(cap) => answer.capped(cap),
+            None => answer.to_not_applicable(),
+        }
+    }
+}
+
+/// Dependencies between controls which are not part of the SOC-CMM, e.g. dependencies-2.3.4.json.
+/// The workbook scores dependent controls like any other, so they are opt-in to keep the scores
+/// of the official schema equal to the workbook.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+pub struct Dependencies {
+    dependencies: HashMap<CID, Dependency>,
+}
+
+impl Dependencies {
+    pub fn new(dependencies: HashMap<CID, Dependency>) -> Self {
+        Self { dependencies }
+    }
+
+    pub fn dependency(&self, cid: &CID) -> Option<&Dependency> {
+        self.dependencies.get(cid)
+    }
+}
+
+/// Recommendations per control which are not part of the SOC-CMM, e.g. recommendations-2.3.4.json.
+/// Kept apart from the schema, which is generated from the workbook. The 2.3.4 recommendations
+/// are a stub so far, they only cover a few charter, governance and process controls.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+pub struct Recommendations {
+    /// How to get from level N (index N-1) to level N+1, at most four entries per control
+    recommendations: HashMap<CID, Vec<String>>,
+}
+
+impl Recommendations {
+    pub fn new(recommendations: HashMap<CID, Vec<String>>) -> Self {
+        Self { recommendations }
+    }
+
+    pub fn recommendations(&self, cid: &CID) -> Option<&Vec<String>> {
+        self.recommendations.get(cid)
+    }
 }
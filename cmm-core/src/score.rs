@@ -1,153 +1,298 @@
-use std::{
-    fmt::Display,
-    iter::{Zip, zip},
-};
+use std::fmt::Display;
 
+use serde::{Deserialize, Serialize};
 use strum::VariantArray;
 
 use crate::{
-    cid::Domain,
+    answer::{Answer, DetailedOptional},
+    cid::{CID, Domain},
     control::Control,
     data::SOCData,
     schema::{ControlSchema, Schema},
+    scoring::{ScoringModel, SocCmm},
 };
 
 pub struct Stats {
     data: SOCData,
     schema: Schema,
+    model: Box<dyn ScoringModel>,
 }
 
 impl Stats {
     pub fn new(data: SOCData, schema: Schema) -> Self {
-        Self { data, schema }
+        Self::with_model(data, schema, Box::new(SocCmm))
+    }
+
+    pub fn with_model(data: SOCData, schema: Schema, model: Box<dyn ScoringModel>) -> Self {
+        Self {
+            data,
+            schema,
+            model,
+        }
+    }
+
+    pub fn data(&self) -> &SOCData {
+        &self.data
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
     }
 
     pub fn score_overall(&self) -> Score {
-        let mut score = 0.0;
-        for &domain in Domain::VARIANTS {
-            score += self.maturity_by_domain(&domain).score();
-            let domain_capability = self.capability_by_domain(&domain).score();
-            if domain_capability.is_normal() {
-                score += domain_capability;
-            }
+        let maturity: Vec<(Domain, Score)> = Domain::VARIANTS
+            .iter()
+            .map(|domain| (*domain, self.maturity_by_domain(domain)))
+            .collect();
+        let capability: Vec<(Domain, Score)> = Domain::VARIANTS
+            .iter()
+            .map(|domain| (*domain, self.capability_by_domain(domain)))
+            .collect();
+        self.model.score_overall(&maturity, &capability)
+    }
+
+    /// Average maturity (0-5) of the domains with a score, the overall maturity level is based on it.
+    /// `score_overall` is not suited for levels, it includes the capabilities of only two domains.
+    pub fn maturity_overall(&self) -> Score {
+        self.average_by_domain(|domain| self.maturity_by_domain(domain), 5.0)
+    }
+
+    /// Average capability (0-3) of the domains with a capability score, i.e. Technology and Services
+    pub fn capability_overall(&self) -> Score {
+        self.average_by_domain(|domain| self.capability_by_domain(domain), 3.0)
+    }
+
+    fn average_by_domain(&self, score_by_domain: impl Fn(&Domain) -> Score, max: f64) -> Score {
+        let scores: Vec<f64> = Domain::VARIANTS
+            .iter()
+            .flat_map(|domain| {
+                let score = score_by_domain(domain);
+                Some(score.score()? / score.max() * max)
+            })
+            .collect();
+        if scores.is_empty() {
+            return Score::not_applicable(max);
         }
-        Score::new(
-            score,
-            Domain::VARIANTS.len() as f64 * 5.0 + Domain::VARIANTS.len() as f64 * 2.0,
-        )
+        Score::new(scores.iter().sum::<f64>() / scores.len() as f64, max)
     }
 
     pub fn capability_by_domain(&self, domain: &Domain) -> Score {
-        Stats::capability_score(self.controls_by_domain(domain))
+        self.model
+            .capability_score(&self.in_scope(self.controls_by_domain(domain)))
     }
 
     pub fn maturity_by_domain(&self, domain: &Domain) -> Score {
-        Stats::maturity_score(self.controls_by_domain(domain))
+        self.model
+            .maturity_score(&self.in_scope(self.controls_by_domain(domain)))
     }
 
     pub fn capability_by_aspect(&self, domain: &Domain, aspect_id: u8) -> Score {
-        Stats::capability_score(self.controls_by_aspect(domain, aspect_id))
+        self.model
+            .capability_score(&self.in_scope(self.controls_by_aspect(domain, aspect_id)))
     }
 
     pub fn maturity_by_aspect(&self, domain: &Domain, aspect_id: u8) -> Score {
-        Stats::maturity_score(self.controls_by_aspect(domain, aspect_id))
+        self.model
+            .maturity_score(&self.in_scope(self.controls_by_aspect(domain, aspect_id)))
+    }
+
+    pub fn distribution_by_domain(&self, domain: &Domain) -> Distribution {
+        self.distribution(self.controls_by_domain(domain))
+    }
+
+    pub fn distribution_by_aspect(&self, domain: &Domain, aspect_id: u8) -> Distribution {
+        self.distribution(self.controls_by_aspect(domain, aspect_id))
+    }
+
+    /// Recommendations for all controls in scope which can still improve, the lowest levels first
+    pub fn recommendations(&self) -> Vec<Recommendation> {
+        let mut recommendations: Vec<Recommendation> = Domain::VARIANTS
+            .iter()
+            .flat_map(|domain| self.controls_by_domain(domain))
+            .filter(|((cid, _control), schema)| !schema.nist_only() && self.data.in_scope(cid))
+            .filter_map(|((cid, control), schema)| {
+                let answer = match schema.depends_on() {
+                    Some(dependency) => dependency.resolve(&self.data, control.answer()),
+                    None => control.answer().clone(),
+                };
+                let level = answer.level()?;
+                Some(Recommendation {
+                    cid: *cid,
+                    level,
+                    text: schema.recommendation(level)?.clone(),
+                })
+            })
+            .collect();
+        recommendations.sort_by_key(|recommendation| (recommendation.level, recommendation.cid));
+        recommendations
     }
 
     fn controls_by_domain(
         &self,
         domain: &Domain,
-    ) -> Zip<impl Iterator<Item = &Control>, impl Iterator<Item = &ControlSchema>> {
-        zip(
-            self.data
-                .controls_by_domain(domain)
-                .map(|(_cid, control)| control),
-            self.schema
-                .controls_by_domain(domain)
-                .map(|(_cid, schema)| schema),
-        )
+    ) -> impl Iterator<Item = ((&CID, &Control), &ControlSchema)> {
+        self.with_schema(self.data.controls_by_domain(domain))
     }
 
     fn controls_by_aspect(
         &self,
         domain: &Domain,
         aspect_id: u8,
-    ) -> Zip<impl Iterator<Item = &Control>, impl Iterator<Item = &ControlSchema>> {
-        zip(
-            self.data
-                .controls_by_aspect(domain, aspect_id)
-                .map(|(_cid, control)| control),
-            self.schema
-                .controls_by_aspect(domain, aspect_id)
-                .map(|(_cid, schema)| schema),
-        )
+    ) -> impl Iterator<Item = ((&CID, &Control), &ControlSchema)> {
+        self.with_schema(self.data.controls_by_aspect(domain, aspect_id))
     }
 
-    fn capability_score<'a, T: IntoIterator<Item = (&'a Control, &'a ControlSchema)>>(
+    /// Pairs controls with their schema by CID, the schema also contains titles without an answer.
+    /// Controls which are not part of the schema are skipped.
+    fn with_schema<'a>(
+        &'a self,
+        controls: impl Iterator<Item = (&'a CID, &'a Control)>,
+    ) -> impl Iterator<Item = ((&'a CID, &'a Control), &'a ControlSchema)> {
+        controls
+            .filter_map(|(cid, control)| Some(((cid, control), self.schema.control_schema(cid)?)))
+    }
+
+    fn distribution<'a, T: IntoIterator<Item = ((&'a CID, &'a Control), &'a ControlSchema)>>(
+        &self,
         controls: T,
-    ) -> Score {
-        let controls_in_scope: Vec<&'a Control> = controls
+    ) -> Distribution {
+        let mut distribution = Distribution::default();
+        for answer in self.in_scope(controls) {
+            match &answer {
+                Answer::DetailedOptional(DetailedOptional::NotRequired) => {
+                    distribution.not_required += 1
+                }
+                answer if answer.is_not_applicable() => distribution.not_applicable += 1,
+                Answer::Bool(true) => distribution.bool_true += 1,
+                Answer::Bool(false) => distribution.bool_false += 1,
+                _ => {}
+            }
+            if let Some(level) = answer.maturity_score() {
+                distribution.maturity[level as usize - 1] += 1;
+            }
+            if answer.capability_in_scope()
+                && let Some(level) = answer.capability_score()
+            {
+                distribution.capability[level as usize - 1] += 1;
+            }
+        }
+        distribution
+    }
+
+    /// Answers which are part of this assessment.
+    /// NIST only controls and controls marked as out of scope are excluded,
+    /// controls with an unmet dependency are resolved to not applicable or capped.
+    fn in_scope<'a, T: IntoIterator<Item = ((&'a CID, &'a Control), &'a ControlSchema)>>(
+        &self,
+        controls: T,
+    ) -> Vec<Answer> {
+        controls
             .into_iter()
-            .filter(|(data, schema)| data.answer().capability_in_scope() && !schema.nist_only())
-            .map(|(data, _schema)| data)
-            .collect();
+            .filter(|((cid, _data), schema)| !schema.nist_only() && self.data.in_scope(cid))
+            .map(|((_cid, data), schema)| match schema.depends_on() {
+                Some(dependency) => dependency.resolve(&self.data, data.answer()),
+                None => data.answer().clone(),
+            })
+            .collect()
+    }
+}
 
-        let count = controls_in_scope.len() as f64;
+/// What to do to move the control `cid` from its current `level` to the next one
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Recommendation {
+    cid: CID,
+    level: u32,
+    text: String,
+}
 
-        let total_score = controls_in_scope
-            .iter()
-            .flat_map(|cap| cap.answer().capability_score())
-            .sum::<u32>() as f64;
-        let max_score = controls_in_scope
-            .iter()
-            .flat_map(|cap| cap.answer().max_score())
-            .sum::<u32>() as f64;
+impl Recommendation {
+    pub fn cid(&self) -> &CID {
+        &self.cid
+    }
 
-        Score::new(3.0 * ((total_score - count) / (max_score - count)), 3.0)
+    pub fn level(&self) -> u32 {
+        self.level
     }
 
-    fn maturity_score<'a, T: IntoIterator<Item = (&'a Control, &'a ControlSchema)>>(
-        controls: T,
-    ) -> Score {
-        let controls_in_scope: Vec<&'a Control> = controls
-            .into_iter()
-            .filter(|(data, schema)| data.answer().maturity_in_scope() && !schema.nist_only())
-            .map(|(data, _schema)| data)
-            .collect();
+    pub fn text(&self) -> &String {
+        &self.text
+    }
+}
 
-        let count = controls_in_scope.len() as f64;
+/// How many answers sit at each level, index 0 is the lowest level (No / Never)
+/// and index 4 the highest (Fully / Always)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Distribution {
+    maturity: [u32; 5],
+    capability: [u32; 5],
+    not_required: u32,
+    not_applicable: u32,
+    bool_true: u32,
+    bool_false: u32,
+}
 
-        let total_score = controls_in_scope
-            .iter()
-            .flat_map(|cap| cap.answer().maturity_score())
-            .sum::<u32>() as f64;
-        let max_score = controls_in_scope
-            .iter()
-            .flat_map(|cap| cap.answer().max_score())
-            .sum::<u32>() as f64;
+impl Distribution {
+    pub fn maturity(&self) -> &[u32; 5] {
+        &self.maturity
+    }
+
+    pub fn capability(&self) -> &[u32; 5] {
+        &self.capability
+    }
+
+    /// Capabilities answered with NotRequired
+    pub fn not_required(&self) -> u32 {
+        self.not_required
+    }
 
-        Score::new(5.0 * ((total_score - count) / (max_score - count)), 5.0)
+    /// Maturity controls answered with NotApplicable
+    pub fn not_applicable(&self) -> u32 {
+        self.not_applicable
+    }
+
+    /// Share of Bool controls answered with true
+    pub fn completeness(&self) -> Score {
+        let total = (self.bool_true + self.bool_false) as f64;
+        if total == 0.0 {
+            return Score::not_applicable(total);
+        }
+        Score::new(self.bool_true as f64, total)
     }
 }
 
 /// Score can be either over an Range of controls (0-5max / 0-3max)
 /// Or over the whole soc-cmm itself, each domain calculated seperately
 /// 5 domains 0-5 = 0-25max
-#[derive(Debug, PartialEq, Clone, Copy)]
+///
+/// A range without any controls in scope has no score, it is not applicable
+/// and serialized as `null`.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub struct Score {
-    score: f64,
+    score: Option<f64>,
     max: f64,
 }
 
 impl Score {
     pub fn new(score: f64, max: f64) -> Self {
-        Self { score, max }
+        Self {
+            score: Some(score),
+            max,
+        }
     }
 
-    pub fn as_percentage(&self) -> f64 {
-        self.score / self.max * 100.0
+    pub fn not_applicable(max: f64) -> Self {
+        Self { score: None, max }
     }
 
-    pub fn score(&self) -> f64 {
+    pub fn is_applicable(&self) -> bool {
+        self.score.is_some()
+    }
+
+    pub fn as_percentage(&self) -> Option<f64> {
+        self.score.map(|score| score / self.max * 100.0)
+    }
+
+    pub fn score(&self) -> Option<f64> {
         self.score
     }
 
@@ -158,15 +303,20 @@ impl Score {
 
 impl Display for Score {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}/{}", &self.score().round(), &self.max().round())
+        match self.score {
+            Some(score) => write!(f, "{}/{}", score.round(), self.max.round()),
+            None => write!(f, "N/A"),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use indexmap::IndexMap;
 
-    use crate::answer::{Answer, DetailedOptional, Satisfaction};
+    use crate::answer::{Detailed, Occurence, Satisfaction};
 
     use super::*;
 
@@ -189,7 +339,7 @@ mod tests {
             })
             .collect();
         let schema = Schema::new(schema);
-        Stats { data, schema }
+        Stats::new(data, schema)
     }
 
     #[test]
@@ -203,9 +353,9 @@ mod tests {
         let stats = stats_from_controls(controls);
 
         let score = stats.capability_by_domain(&Domain::Business);
-        assert_eq!(score.score(), 3.0);
+        assert_eq!(score.score(), Some(3.0));
         assert_eq!(score.max(), 3.0);
-        assert_eq!(score.as_percentage(), 100.0);
+        assert_eq!(score.as_percentage(), Some(100.0));
     }
 
     #[test]
@@ -224,14 +374,14 @@ mod tests {
 
         let score = stats.capability_by_domain(&Domain::Business);
 
-        assert_eq!(score.score(), 3.0);
+        assert_eq!(score.score(), Some(3.0));
         assert_eq!(score.max(), 3.0);
-        assert_eq!(score.as_percentage(), 100.0);
+        assert_eq!(score.as_percentage(), Some(100.0));
 
         let score = stats.maturity_by_domain(&Domain::Business);
-        assert_eq!(score.score(), 5.0);
+        assert_eq!(score.score(), Some(5.0));
         assert_eq!(score.max(), 5.0);
-        assert_eq!(score.as_percentage(), 100.0);
+        assert_eq!(score.as_percentage(), Some(100.0));
     }
 
     #[test]
@@ -249,8 +399,256 @@ mod tests {
         let stats = stats_from_controls(controls);
 
         let score = stats.capability_by_domain(&Domain::Business);
-        assert_eq!(score.score(), 3.0);
+        assert_eq!(score.score(), Some(3.0));
+        assert_eq!(score.max(), 3.0);
+        assert_eq!(score.as_percentage(), Some(100.0));
+    }
+
+    #[test]
+    fn test_distribution() {
+        let controls = vec![
+            Control::new(
+                Answer::DetailedOptional(DetailedOptional::NotRequired),
+                None,
+            ),
+            Control::new(Answer::DetailedOptional(DetailedOptional::Fully), None),
+            Control::new(Answer::DetailedOptional(DetailedOptional::No), None),
+            Control::new(Answer::Detailed(Detailed::Partially), None),
+            Control::new(Answer::Detailed(Detailed::NotApplicable), None),
+            Control::new(Answer::Satisfaction(Satisfaction::Somewhat), None),
+            Control::new(Answer::Bool(true), None),
+            Control::new(Answer::Bool(false), None),
+            Control::new(Answer::Bool(false), None),
+        ];
+
+        let stats = stats_from_controls(controls);
+
+        let distribution = stats.distribution_by_domain(&Domain::Business);
+        assert_eq!(distribution.capability(), &[1, 0, 0, 0, 1]);
+        assert_eq!(distribution.maturity(), &[0, 2, 0, 0, 0]);
+        assert_eq!(distribution.not_required(), 1);
+        assert_eq!(distribution.not_applicable(), 1);
+        assert_eq!(distribution.completeness().score(), Some(1.0));
+        assert_eq!(distribution.completeness().max(), 3.0);
+    }
+
+    #[test]
+    fn test_not_applicable_score() {
+        let controls = vec![
+            Control::new(
+                Answer::DetailedOptional(DetailedOptional::NotRequired),
+                None,
+            ),
+            Control::new(Answer::Bool(true), None),
+        ];
+
+        let stats = stats_from_controls(controls);
+
+        let score = stats.capability_by_domain(&Domain::Business);
+        assert!(!score.is_applicable());
         assert_eq!(score.max(), 3.0);
-        assert_eq!(score.as_percentage(), 100.0);
+        assert_eq!(score.as_percentage(), None);
+        assert_eq!(score.to_string(), "N/A");
+        assert_eq!(
+            serde_json::to_string(&score).unwrap(),
+            r#"{"score":null,"max":3.0}"#
+        );
+        assert!(!stats.maturity_by_domain(&Domain::Business).is_applicable());
+        assert!(!stats.score_overall().is_applicable());
+        assert!(
+            !stats
+                .distribution_by_domain(&Domain::People)
+                .completeness()
+                .is_applicable()
+        );
+    }
+
+    #[test]
+    fn test_overall_ignores_not_applicable() {
+        let controls = vec![
+            Control::new(Answer::Satisfaction(Satisfaction::Fully), None),
+            Control::new(
+                Answer::DetailedOptional(DetailedOptional::NotRequired),
+                None,
+            ),
+        ];
+
+        let stats = stats_from_controls(controls);
+
+        let score = stats.score_overall();
+        assert_eq!(score.score(), Some(5.0));
+        assert_eq!(score.max(), 35.0);
+    }
+
+    #[test]
+    fn test_maturity_overall() {
+        let mut controls: IndexMap<CID, Control> = IndexMap::new();
+        for (cid, answer) in [
+            ("Business.1.1", Satisfaction::Fully),
+            ("People.1.1", Satisfaction::No),
+        ] {
+            controls.insert(
+                cid.parse().unwrap(),
+                Control::new(Answer::Satisfaction(answer), None),
+            );
+        }
+        let schema = Schema::new(
+            controls
+                .keys()
+                .map(|cid| (*cid, ControlSchema::default()))
+                .collect(),
+        );
+        let stats = Stats::new(SOCData::new(controls, None, IndexMap::new()), schema);
+
+        // Business 5, People 0, the other domains have no score
+        let score = stats.maturity_overall();
+        assert_eq!(score.score(), Some(2.5));
+        assert_eq!(score.max(), 5.0);
+        assert!(
+            !stats_from_controls(Vec::new())
+                .maturity_overall()
+                .is_applicable()
+        );
+    }
+
+    #[test]
+    fn test_capability_overall() {
+        let mut controls: IndexMap<CID, Control> = IndexMap::new();
+        for (cid, answer) in [
+            ("Technology.1.1", DetailedOptional::Fully),
+            ("Services.1.1", DetailedOptional::No),
+        ] {
+            controls.insert(
+                cid.parse().unwrap(),
+                Control::new(Answer::DetailedOptional(answer), None),
+            );
+        }
+        let schema = Schema::new(
+            controls
+                .keys()
+                .map(|cid| (*cid, ControlSchema::default()))
+                .collect(),
+        );
+        let stats = Stats::new(SOCData::new(controls, None, IndexMap::new()), schema);
+
+        // Technology 3, Services 0, the other domains have no capability
+        let score = stats.capability_overall();
+        assert_eq!(score.score(), Some(1.5));
+        assert_eq!(score.max(), 3.0);
+        assert!(
+            !stats_from_controls(Vec::new())
+                .capability_overall()
+                .is_applicable()
+        );
+    }
+
+    #[test]
+    fn test_out_of_scope() {
+        let controls = vec![
+            Control::new(Answer::Satisfaction(Satisfaction::Fully), None),
+            Control::new(Answer::Satisfaction(Satisfaction::No), None),
+        ];
+
+        let mut stats = stats_from_controls(controls);
+        stats.data.toggle_scope(&"Business.2".parse().unwrap());
+
+        assert_eq!(
+            stats.maturity_by_domain(&Domain::Business).score(),
+            Some(5.0)
+        );
+        assert!(
+            !stats
+                .maturity_by_aspect(&Domain::Business, 2)
+                .is_applicable()
+        );
+        assert_eq!(
+            stats.distribution_by_domain(&Domain::Business).maturity(),
+            &[0, 0, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn test_not_applicable_maturity() {
+        let controls = vec![
+            Control::new(Answer::Detailed(Detailed::NotApplicable), None),
+            Control::new(Answer::Detailed(Detailed::Fully), None),
+        ];
+
+        let stats = stats_from_controls(controls);
+
+        assert_eq!(
+            stats.maturity_by_domain(&Domain::Business).score(),
+            Some(5.0)
+        );
+    }
+
+    #[test]
+    fn test_dependency() {
+        let controls = vec![
+            Control::new(Answer::Detailed(Detailed::No), None),
+            Control::new(Answer::Detailed(Detailed::Fully), None),
+            Control::new(Answer::Occurence(Occurence::Always), None),
+        ];
+        let mut stats = stats_from_controls(controls);
+        let dependent: ControlSchema = serde_json::from_str(
+            r#"{"title": "", "control_type": "Detailed", "depends_on": {"parent": "Business.1"}}"#,
+        )
+        .unwrap();
+        let capped: ControlSchema = serde_json::from_str(
+            r#"{"title": "", "control_type": "Occurence", "depends_on": {"parent": "Business.1", "cap": 2}}"#,
+        )
+        .unwrap();
+        stats.schema = Schema::new(HashMap::from([
+            ("Business.1".parse().unwrap(), ControlSchema::default()),
+            ("Business.2".parse().unwrap(), dependent),
+            ("Business.3".parse().unwrap(), capped),
+        ]));
+
+        // Business.2 is not applicable, Business.3 is capped at Sometimes
+        assert_eq!(
+            stats.maturity_by_domain(&Domain::Business).score(),
+            Some(0.625)
+        );
+        assert_eq!(
+            stats
+                .distribution_by_domain(&Domain::Business)
+                .not_applicable(),
+            1
+        );
+
+        stats.data.set_answer(
+            &"Business.1".parse().unwrap(),
+            Answer::Detailed(Detailed::Fully),
+        );
+        assert_eq!(
+            stats.maturity_by_domain(&Domain::Business).score(),
+            Some(5.0)
+        );
+    }
+
+    #[test]
+    fn test_recommendations() {
+        let controls = vec![
+            Control::new(Answer::Detailed(Detailed::Mostly), None),
+            Control::new(Answer::Detailed(Detailed::Partially), None),
+            Control::new(Answer::Detailed(Detailed::Fully), None),
+        ];
+        let mut stats = stats_from_controls(controls);
+        let schema: ControlSchema = serde_json::from_str(
+            r#"{"title": "", "control_type": "Detailed", "recommendations": ["one", "two", "three", "four"]}"#,
+        )
+        .unwrap();
+        stats.schema = Schema::new(HashMap::from([
+            ("Business.1".parse().unwrap(), schema.clone()),
+            ("Business.2".parse().unwrap(), schema.clone()),
+            ("Business.3".parse().unwrap(), schema),
+        ]));
+
+        let recommendations = stats.recommendations();
+        assert_eq!(recommendations.len(), 2);
+        assert_eq!(recommendations[0].cid(), &"Business.2".parse().unwrap());
+        assert_eq!(recommendations[0].text(), "two");
+        assert_eq!(recommendations[1].level(), 4);
+        assert_eq!(recommendations[1].text(), "four");
     }
 }
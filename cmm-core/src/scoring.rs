@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use strum::VariantArray;
+
+use crate::{answer::Answer, cid::Domain, score::Score};
+
+/// Aggregation of answers into scores. `Stats` filters the controls which are out of scope
+/// for the assessment (e.g. NIST only) and hands the remaining answers to the model.
+pub trait ScoringModel {
+    /// Maturity score (0-5) over a range of answers, e.g. an aspect or a domain
+    fn maturity_score(&self, answers: &[Answer]) -> Score;
+
+    /// Capability score (0-3) over a range of answers, e.g. an aspect or a domain
+    fn capability_score(&self, answers: &[Answer]) -> Score;
+
+    /// Aggregation of the domain scores into a single score
+    fn score_overall(&self, maturity: &[(Domain, Score)], capability: &[(Domain, Score)]) -> Score;
+}
+
+/// Models which can be selected by the user
+#[derive(
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    VariantArray,
+    strum::Display,
+    strum::EnumString,
+)]
+pub enum ScoringModelKind {
+    #[default]
+    #[strum(serialize = "SocCmm", to_string = "SOC-CMM 2.3.4")]
+    SocCmm,
+    #[strum(serialize = "WeakestLink", to_string = "Weakest link")]
+    WeakestLink,
+    #[strum(serialize = "WeightedDomains", to_string = "Weighted domains")]
+    WeightedDomains,
+}
+
+impl ScoringModelKind {
+    /// `weights` are only used by the weighted domains model
+    pub fn model(&self, weights: &WeightedDomains) -> Box<dyn ScoringModel> {
+        match self {
+            ScoringModelKind::SocCmm => Box::new(SocCmm),
+            ScoringModelKind::WeakestLink => Box::new(WeakestLink),
+            ScoringModelKind::WeightedDomains => Box::new(weights.clone()),
+        }
+    }
+}
+
+/// Scoring as done by the SOC-CMM 2.3.4 Excel tool
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SocCmm;
+
+impl ScoringModel for SocCmm {
+    fn maturity_score(&self, answers: &[Answer]) -> Score {
+        normalized_average(
+            answers
+                .iter()
+                .filter(|answer| answer.maturity_in_scope())
+                .flat_map(|answer| answer.maturity_score().zip(answer.max_score())),
+            5.0,
+        )
+    }
+
+    fn capability_score(&self, answers: &[Answer]) -> Score {
+        normalized_average(
+            answers
+                .iter()
+                .filter(|answer| answer.capability_in_scope())
+                .flat_map(|answer| answer.capability_score().zip(answer.max_score())),
+            3.0,
+        )
+    }
+
+    /// Sum of all applicable domain scores, domains without data do not contribute
+    fn score_overall(&self, maturity: &[(Domain, Score)], capability: &[(Domain, Score)]) -> Score {
+        let max = Domain::VARIANTS.len() as f64 * 5.0 + Domain::VARIANTS.len() as f64 * 2.0;
+        let scores: Vec<f64> = maturity
+            .iter()
+            .chain(capability)
+            .flat_map(|(_domain, score)| score.score())
+            .collect();
+        if scores.is_empty() {
+            return Score::not_applicable(max);
+        }
+        Score::new(scores.iter().sum(), max)
+    }
+}
+
+/// A range is only as mature as its weakest answer
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WeakestLink;
+
+impl ScoringModel for WeakestLink {
+    fn maturity_score(&self, answers: &[Answer]) -> Score {
+        weakest(
+            answers
+                .iter()
+                .filter(|answer| answer.maturity_in_scope())
+                .flat_map(|answer| answer.maturity_score().zip(answer.max_score())),
+            5.0,
+        )
+    }
+
+    fn capability_score(&self, answers: &[Answer]) -> Score {
+        weakest(
+            answers
+                .iter()
+                .filter(|answer| answer.capability_in_scope())
+                .flat_map(|answer| answer.capability_score().zip(answer.max_score())),
+            3.0,
+        )
+    }
+
+    /// Lowest domain maturity
+    fn score_overall(
+        &self,
+        maturity: &[(Domain, Score)],
+        _capability: &[(Domain, Score)],
+    ) -> Score {
+        maturity
+            .iter()
+            .flat_map(|(_domain, score)| score.score())
+            .reduce(f64::min)
+            .map(|score| Score::new(score, 5.0))
+            .unwrap_or(Score::not_applicable(5.0))
+    }
+}
+
+/// Like the SOC-CMM, but the overall score is the weighted average of the domain maturities
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct WeightedDomains {
+    weights: HashMap<Domain, f64>,
+}
+
+impl Default for WeightedDomains {
+    fn default() -> Self {
+        Self {
+            weights: Domain::VARIANTS
+                .iter()
+                .map(|domain| (*domain, 1.0))
+                .collect(),
+        }
+    }
+}
+
+impl WeightedDomains {
+    /// Domains without a weight are ignored
+    pub fn new(weights: HashMap<Domain, f64>) -> Self {
+        Self { weights }
+    }
+
+    pub fn weight(&self, domain: &Domain) -> f64 {
+        self.weights.get(domain).copied().unwrap_or_default()
+    }
+
+    /// Negative weights are stored as 0, which ignores the domain
+    pub fn set_weight(&mut self, domain: Domain, weight: f64) {
+        self.weights.insert(domain, weight.max(0.0));
+    }
+}
+
+impl ScoringModel for WeightedDomains {
+    fn maturity_score(&self, answers: &[Answer]) -> Score {
+        SocCmm.maturity_score(answers)
+    }
+
+    fn capability_score(&self, answers: &[Answer]) -> Score {
+        SocCmm.capability_score(answers)
+    }
+
+    fn score_overall(
+        &self,
+        maturity: &[(Domain, Score)],
+        _capability: &[(Domain, Score)],
+    ) -> Score {
+        let (weighted, total_weight) = maturity
+            .iter()
+            .flat_map(|(domain, score)| Some((score.score()?, *self.weights.get(domain)?)))
+            .fold((0.0, 0.0), |(sum, total), (score, weight)| {
+                (sum + score * weight, total + weight)
+            });
+        if total_weight == 0.0 {
+            return Score::not_applicable(5.0);
+        }
+        Score::new(weighted / total_weight, 5.0)
+    }
+}
+
+/// (score, max) pairs where the lowest score is 1 mapped to 0..scale
+fn normalized_average<I: Iterator<Item = (u32, u32)>>(answers: I, scale: f64) -> Score {
+    let (count, total_score, max_score) = answers.fold(
+        (0.0, 0.0, 0.0),
+        |(count, total, max), (score, answer_max)| {
+            (count + 1.0, total + score as f64, max + answer_max as f64)
+        },
+    );
+    if count == 0.0 {
+        return Score::not_applicable(scale);
+    }
+    Score::new(scale * ((total_score - count) / (max_score - count)), scale)
+}
+
+fn weakest<I: Iterator<Item = (u32, u32)>>(answers: I, scale: f64) -> Score {
+    answers
+        .map(|(score, max)| scale * (score as f64 - 1.0) / (max as f64 - 1.0))
+        .reduce(f64::min)
+        .map(|score| Score::new(score, scale))
+        .unwrap_or(Score::not_applicable(scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::{Detailed, DetailedOptional};
+
+    use super::*;
+
+    fn answers() -> Vec<Answer> {
+        vec![
+            Answer::Detailed(Detailed::Fully),
+            Answer::Detailed(Detailed::Averagely),
+            Answer::DetailedOptional(DetailedOptional::Mostly),
+            Answer::DetailedOptional(DetailedOptional::NotRequired),
+        ]
+    }
+
+    #[test]
+    fn test_soc_cmm() {
+        let answers = answers();
+        assert_eq!(SocCmm.maturity_score(&answers).score(), Some(3.75));
+        assert_eq!(SocCmm.capability_score(&answers).score(), Some(2.25));
+    }
+
+    #[test]
+    fn test_weakest_link() {
+        let answers = answers();
+        assert_eq!(WeakestLink.maturity_score(&answers).score(), Some(2.5));
+        assert_eq!(
+            WeakestLink
+                .score_overall(
+                    &[
+                        (Domain::Business, Score::new(4.0, 5.0)),
+                        (Domain::People, Score::new(1.5, 5.0)),
+                        (Domain::Process, Score::not_applicable(5.0)),
+                    ],
+                    &[],
+                )
+                .score(),
+            Some(1.5)
+        );
+    }
+
+    #[test]
+    fn test_weighted_domains() {
+        let model = WeightedDomains::new(HashMap::from([
+            (Domain::Business, 3.0),
+            (Domain::People, 1.0),
+        ]));
+        let overall = model.score_overall(
+            &[
+                (Domain::Business, Score::new(4.0, 5.0)),
+                (Domain::People, Score::new(2.0, 5.0)),
+                (Domain::Process, Score::new(0.0, 5.0)),
+            ],
+            &[],
+        );
+        assert_eq!(overall.score(), Some(3.5));
+        assert_eq!(overall.max(), 5.0);
+    }
+
+    #[test]
+    fn test_configured_weights() {
+        let mut weights = WeightedDomains::default();
+        weights.set_weight(Domain::People, 0.0);
+        weights.set_weight(Domain::Process, -1.0);
+        assert_eq!(weights.weight(&Domain::Business), 1.0);
+        assert_eq!(weights.weight(&Domain::Process), 0.0);
+
+        let model = ScoringModelKind::WeightedDomains.model(&weights);
+        let overall = model.score_overall(
+            &[
+                (Domain::Business, Score::new(4.0, 5.0)),
+                (Domain::People, Score::new(0.0, 5.0)),
+                (Domain::Process, Score::new(0.0, 5.0)),
+            ],
+            &[],
+        );
+        assert_eq!(overall.score(), Some(4.0));
+
+        let json = serde_json::to_string(&weights).unwrap();
+        assert_eq!(
+            serde_json::from_str::<WeightedDomains>(&json).unwrap(),
+            weights
+        );
+    }
+}
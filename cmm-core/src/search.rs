@@ -0,0 +1,304 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+use strum::VariantArray;
+
+use crate::{
+    cid::{CID, Domain},
+    data::SOCData,
+    schema::Schema,
+};
+
+/// Something a search result can link to
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum SearchTarget {
+    Control(CID),
+    /// Domain and aspect id (index+1)
+    Aspect(Domain, u8),
+}
+
+impl SearchTarget {
+    /// HTML id of the rendered control or aspect heading
+    pub fn anchor(&self) -> String {
+        match self {
+            SearchTarget::Control(cid) => cid.to_string(),
+            SearchTarget::Aspect(domain, aspect_id) => format!("aspect-{domain}-{aspect_id}"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, strum::Display)]
+pub enum SearchField {
+    Title,
+    Aspect,
+    Guidance,
+    Remarks,
+    Comment,
+}
+
+impl SearchField {
+    fn weight(&self) -> f64 {
+        match self {
+            SearchField::Title | SearchField::Aspect => 3.0,
+            SearchField::Comment => 2.0,
+            SearchField::Guidance | SearchField::Remarks => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SearchResult {
+    target: SearchTarget,
+    /// Field with the best match
+    field: SearchField,
+    /// Text of the field with the best match
+    text: String,
+    score: f64,
+}
+
+impl SearchResult {
+    pub fn target(&self) -> &SearchTarget {
+        &self.target
+    }
+
+    pub fn field(&self) -> SearchField {
+        self.field
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+impl Display for SearchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}): {}", self.target.anchor(), self.field, self.text)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    target: SearchTarget,
+    field: SearchField,
+    text: String,
+    words: Vec<String>,
+}
+
+/// Full-text index over the schema texts, aspect names and the comments of the answers
+#[derive(Debug, Clone)]
+pub struct SearchIndex {
+    entries: Vec<Entry>,
+}
+
+impl SearchIndex {
+    pub fn new(schema: &Schema, data: &SOCData) -> Self {
+        let mut entries = Vec::new();
+        let mut push = |target: SearchTarget, field: SearchField, text: &str| {
+            if text.trim().is_empty() {
+                return;
+            }
+            entries.push(Entry {
+                target,
+                field,
+                text: text.to_owned(),
+                words: tokenize(text),
+            });
+        };
+
+        for domain in Domain::VARIANTS {
+            for (i, aspect) in schema.aspects(domain).iter().enumerate() {
+                push(
+                    SearchTarget::Aspect(*domain, i as u8 + 1),
+                    SearchField::Aspect,
+                    aspect,
+                );
+            }
+        }
+
+        for (cid, control_schema) in schema.controls().iter().sorted_by_key(|(cid, _)| *cid) {
+            let target = SearchTarget::Control(*cid);
+            push(target, SearchField::Title, control_schema.title());
+            if let Some(remarks) = control_schema.remarks() {
+                push(target, SearchField::Remarks, remarks);
+            }
+            for guidance in control_schema.guidances() {
+                push(target, SearchField::Guidance, guidance);
+            }
+            let Some(control) = data.control(cid) else {
+                continue;
+            };
+            if let Some(comment) = control.comment() {
+                push(target, SearchField::Comment, comment);
+            }
+            for comment in control.comments() {
+                push(target, SearchField::Comment, comment.text());
+            }
+        }
+
+        Self { entries }
+    }
+
+    /// Ranked results, best first. Every word of the query has to match (fuzzy) in one entry.
+    pub fn search(&self, query: &str) -> Vec<SearchResult> {
+        let query = tokenize(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut results: Vec<SearchResult> = Vec::new();
+        for entry in &self.entries {
+            let Some(score) = query
+                .iter()
+                .map(|term| {
+                    entry
+                        .words
+                        .iter()
+                        .map(|word| word_score(term, word))
+                        .fold(0.0, f64::max)
+                })
+                .try_fold(0.0, |sum, score| (score > 0.0).then_some(sum + score))
+            else {
+                continue;
+            };
+            let score = score * entry.field.weight();
+
+            match results.iter_mut().find(|result| result.target == entry.target) {
+                Some(result) if result.score < score => {
+                    result.field = entry.field;
+                    result.text = entry.text.clone();
+                    result.score = score;
+                }
+                Some(_) => {}
+                None => results.push(SearchResult {
+                    target: entry.target,
+                    field: entry.field,
+                    text: entry.text.clone(),
+                    score,
+                }),
+            }
+        }
+
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// 1.0 for an exact match, less for prefix, substring and typo matches and 0.0 for no match
+fn word_score(term: &str, word: &str) -> f64 {
+    if term == word {
+        return 1.0;
+    }
+    if word.starts_with(term) {
+        return 0.8;
+    }
+    if term.len() >= 3 && word.contains(term) {
+        return 0.6;
+    }
+    let allowed_typos = match term.chars().count() {
+        0..4 => 0,
+        4..8 => 1,
+        _ => 2,
+    };
+    if allowed_typos > 0 && levenshtein(term, word) <= allowed_typos {
+        return 0.5;
+    }
+    0.0
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, char_a) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, char_b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(char_a != *char_b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use crate::{answer::Answer, control::Control};
+
+    use super::*;
+
+    fn index() -> SearchIndex {
+        let schema: Schema = serde_json::from_str(
+            r#"{
+                "aspects": { "Process": ["Operations & Facilities"] },
+                "control_schemas": {
+                    "Process.1.1": {
+                        "title": "Do you have a playbook for incidents?",
+                        "control_type": "Detailed",
+                        "guidances": ["No playbooks", "All incidents are covered"]
+                    },
+                    "Process.1.2": {
+                        "title": "Is the facility secured?",
+                        "control_type": "Detailed",
+                        "remarks": "Physical security of the SOC room"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let mut controls = IndexMap::new();
+        controls.insert(
+            "Process.1.2".parse().unwrap(),
+            Control::new(
+                Answer::Any(String::new()),
+                Some(String::from("Badge readers installed")),
+            ),
+        );
+        SearchIndex::new(&schema, &SOCData::new(controls, None, IndexMap::new()))
+    }
+
+    #[test]
+    fn test_search_ranking() {
+        let results = index().search("playbook");
+        assert_eq!(
+            results[0].target(),
+            &SearchTarget::Control("Process.1.1".parse().unwrap())
+        );
+        assert_eq!(results[0].field(), SearchField::Title);
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_search_fuzzy() {
+        assert_eq!(index().search("plabyook").len(), 1);
+        assert!(index().search("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_search_comments_and_aspects() {
+        let results = index().search("badge");
+        assert_eq!(results[0].field(), SearchField::Comment);
+        assert_eq!(results[0].target().anchor(), "Process.1.2");
+
+        let results = index().search("operations");
+        assert_eq!(results[0].target().anchor(), "aspect-Process-1");
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("abc", "abc"), 0);
+    }
+}
@@ -1,9 +1,14 @@
 use cmm_core::{
-    answer::{Answer, DetailedOptional},
-    cid::CID,
-    control::Control,
+    action::{Action, ActionStatus},
+    answer::{Answer, Detailed, DetailedOptional, Occurence},
+    cid::{CID, Domain},
+    control::{Comment, Control},
     data::SOCData,
-    schema::Schema,
+    lint::{Lint, LintRule},
+    narrative::NarrativeSection,
+    policy::{Policy, PolicyRule},
+    schema::{Dependencies, Recommendations, Schema},
+    score::Stats,
 };
 use indexmap::IndexMap;
 use pretty_assertions::assert_eq;
@@ -53,3 +58,314 @@ Line breaks **woo**!
 fn test_soc_cmm_2_3_4() {
     serde_json::from_str::<Schema>(include_str!("../../scheme-2.3.4.json")).unwrap();
 }
+
+#[test]
+fn test_comment_thread() {
+    let src = r#"["Business.1.2"]
+type = "Detailed"
+answer = "Mostly"
+comment = "Imported from Excel"
+
+[["Business.1.2".comments]]
+author = "Assessor"
+timestamp = "2025-01-31T12:00:00.000Z"
+text = "Is there a signed document?"
+resolved = true
+
+[["Business.1.2".comments]]
+author = "SOC Lead"
+timestamp = "2025-02-01T08:30:00.000Z"
+text = "Yes, see the charter."
+"#;
+    let mut parsed_cmm: SOCData = toml::from_str(src).unwrap();
+    let cid: CID = "Business.1.2".parse().unwrap();
+    let control = parsed_cmm.control(&cid).unwrap();
+    assert_eq!(control.comment(), &Some(String::from("Imported from Excel")));
+    assert_eq!(control.comments().len(), 2);
+    assert_eq!(control.open_comments(), 1);
+    assert_eq!(
+        parsed_cmm,
+        toml::from_str(&toml::to_string(&parsed_cmm).unwrap()).unwrap()
+    );
+
+    parsed_cmm.add_comment(
+        &cid,
+        Comment::new(
+            String::from("Assessor"),
+            String::from("2025-02-02T10:00:00.000Z"),
+            String::from("Thanks!"),
+        ),
+    );
+    parsed_cmm.toggle_comment_resolved(&cid, 1);
+    assert_eq!(parsed_cmm.control(&cid).unwrap().open_comments(), 1);
+}
+
+#[test]
+fn test_tags() {
+    let mut controls: IndexMap<CID, Control> = IndexMap::new();
+    controls.insert(
+        "Business.1.1".parse().unwrap(),
+        Control::new(Answer::DetailedOptional(DetailedOptional::No), None),
+    );
+    controls.insert(
+        "Business.1.2".parse().unwrap(),
+        Control::new(Answer::DetailedOptional(DetailedOptional::Fully), None),
+    );
+    let mut cmm = SOCData::new(controls, None, IndexMap::new());
+    let first: CID = "Business.1.1".parse().unwrap();
+    let second: CID = "Business.1.2".parse().unwrap();
+
+    cmm.add_tag(&first, "quick-win");
+    cmm.add_tag(&first, " quick-win ");
+    cmm.add_tag(&first, "");
+    cmm.add_tag(&second, "budget-2027");
+    cmm.add_tag(&second, "quick-win");
+
+    assert_eq!(cmm.control(&first).unwrap().tags(), &vec!["quick-win"]);
+    assert_eq!(cmm.tags(), vec!["budget-2027", "quick-win"]);
+    assert_eq!(
+        cmm.controls_by_tag("quick-win")
+            .map(|(cid, _)| *cid)
+            .collect::<Vec<_>>(),
+        vec![first, second]
+    );
+
+    cmm.remove_tag(&second, "quick-win");
+    assert_eq!(cmm.controls_by_tag("quick-win").count(), 1);
+    cmm.remove_tag(&first, " quick-win ");
+    assert_eq!(cmm.controls_by_tag("quick-win").count(), 0);
+    assert!(
+        toml::to_string(&cmm)
+            .unwrap()
+            .contains(r#"tags = ["budget-2027"]"#)
+    );
+}
+
+#[test]
+fn test_action_plan() {
+    let mut controls: IndexMap<CID, Control> = IndexMap::new();
+    for cid in ["Business.1.1", "People.1.1", "Business.2.1", "Process.1.1"] {
+        controls.insert(
+            cid.parse().unwrap(),
+            Control::new(Answer::DetailedOptional(DetailedOptional::No), None),
+        );
+    }
+    let mut cmm = SOCData::new(controls, None, IndexMap::new());
+    let action = |due: Option<&str>| {
+        Some(Action::new(
+            String::from("SOC Lead"),
+            due.map(|due| due.parse().unwrap()),
+            ActionStatus::Open,
+            String::from("Document the process"),
+        ))
+    };
+    cmm.set_action(&"People.1.1".parse().unwrap(), action(Some("2026-01-01")));
+    cmm.set_action(&"Business.2.1".parse().unwrap(), action(None));
+    cmm.set_action(&"Process.1.1".parse().unwrap(), action(Some("2025-06-30")));
+    cmm.set_action(&"Business.1.1".parse().unwrap(), action(Some("2026-01-01")));
+
+    assert_eq!(
+        cmm.action_plan()
+            .into_iter()
+            .map(|(cid, _action)| cid.to_string())
+            .collect::<Vec<_>>(),
+        vec!["Process.1.1", "Business.1.1", "People.1.1", "Business.2.1"]
+    );
+    assert_eq!(cmm, toml::from_str(&toml::to_string(&cmm).unwrap()).unwrap());
+}
+
+#[test]
+fn test_scope() {
+    let src = r#"out_of_scope = ["Services.5", "Technology.4.2"]
+
+["Services.5.1"]
+type = "Detailed"
+answer = "Mostly"
+"#;
+    let mut parsed_cmm: SOCData = toml::from_str(src).unwrap();
+    assert!(!parsed_cmm.in_scope(&"Services.5.1".parse().unwrap()));
+    assert!(!parsed_cmm.in_scope(&"Technology.4.2.1".parse().unwrap()));
+    assert!(parsed_cmm.in_scope(&"Technology.4.1".parse().unwrap()));
+    assert!(parsed_cmm.control(&"Services.5.1".parse().unwrap()).is_some());
+
+    parsed_cmm.toggle_scope(&"Services.5".parse().unwrap());
+    assert!(parsed_cmm.in_scope(&"Services.5.1".parse().unwrap()));
+    assert!(
+        toml::to_string(&parsed_cmm)
+            .unwrap()
+            .starts_with(r#"out_of_scope = ["Technology.4.2"]"#)
+    );
+}
+
+#[test]
+fn test_not_applicable_justification() {
+    let mut controls: IndexMap<CID, Control> = IndexMap::new();
+    let cid: CID = "Business.1.2".parse().unwrap();
+    controls.insert(
+        cid,
+        Control::new(Answer::Detailed(Detailed::NotApplicable), None),
+    );
+    let mut cmm = SOCData::new(controls, None, IndexMap::new());
+    assert_eq!(cmm.missing_justifications().collect::<Vec<_>>(), vec![&cid]);
+    assert!(cmm.finalize().is_err());
+
+    cmm.set_comment(&cid, Some(String::from("Single site SOC")));
+    assert_eq!(cmm.missing_justifications().count(), 0);
+    assert!(cmm.finalize().is_ok());
+}
+
+#[test]
+fn test_lint_rules() {
+    let lint: Lint = serde_json::from_str(include_str!("../../lint-2.3.4.json")).unwrap();
+    let schema: Schema =
+        serde_json::from_str::<Schema>(include_str!("../../scheme-2.3.4.json")).unwrap();
+    for rule in lint.rules() {
+        let cids = match rule {
+            LintRule::Contradiction {
+                control, parent, ..
+            } => [control, parent],
+            LintRule::ExceedsCompleteness {
+                control, section, ..
+            } => [control, section],
+            LintRule::NotDeployed {
+                control, checklist, ..
+            } => [control, checklist],
+        };
+        for cid in cids {
+            assert!(
+                schema.control_schema(cid).is_some(),
+                "{cid} is not in the schema"
+            );
+        }
+    }
+    let data = SOCData::from(&schema);
+    assert!(lint.check(&data).is_empty());
+}
+
+/// The workbook scores dependent controls even if their parent is answered with No
+#[test]
+fn test_recommendations_are_opt_in() {
+    let schema: Schema =
+        serde_json::from_str::<Schema>(include_str!("../../scheme-2.3.4.json")).unwrap();
+    let recommendations: Recommendations =
+        serde_json::from_str(include_str!("../../recommendations-2.3.4.json")).unwrap();
+    let charter: CID = "Business.3.1".parse().unwrap();
+    let data = SOCData::from(&schema);
+    assert!(
+        Stats::new(data.clone(), schema.clone())
+            .recommendations()
+            .is_empty()
+    );
+
+    let schema = schema.with_recommendations(&recommendations);
+    assert_eq!(
+        schema
+            .control_schema(&charter)
+            .unwrap()
+            .recommendations()
+            .len(),
+        4
+    );
+    let recommendations = Stats::new(data, schema).recommendations();
+    assert!(
+        recommendations
+            .iter()
+            .any(|recommendation| recommendation.cid() == &charter)
+    );
+}
+
+#[test]
+fn test_dependencies_are_opt_in() {
+    let schema: Schema =
+        serde_json::from_str::<Schema>(include_str!("../../scheme-2.3.4.json")).unwrap();
+    let dependencies: Dependencies =
+        serde_json::from_str(include_str!("../../dependencies-2.3.4.json")).unwrap();
+    let mut data = SOCData::from(&schema);
+    data.set_answer(
+        &"Business.3.3".parse().unwrap(),
+        Answer::Occurence(Occurence::Always),
+    );
+    data.set_answer(
+        &"Business.3.4".parse().unwrap(),
+        Answer::Detailed(Detailed::Fully),
+    );
+
+    // Business.3.1 and 3.5 are No (1), 3.3 and 3.4 are 5: 5 * (12 - 4) / (20 - 4), as in the workbook
+    let stats = Stats::new(data.clone(), schema.clone());
+    assert_eq!(
+        stats.maturity_by_aspect(&Domain::Business, 3).score(),
+        Some(2.5)
+    );
+
+    // Business.3.3 and 3.4 depend on the charter in Business.3.1
+    let stats = Stats::new(data, schema.with_dependencies(&dependencies));
+    assert_eq!(
+        stats.maturity_by_aspect(&Domain::Business, 3).score(),
+        Some(0.0)
+    );
+}
+
+#[test]
+fn test_policy_finalize() {
+    let mut controls: IndexMap<CID, Control> = IndexMap::new();
+    let cid: CID = "Business.1.2".parse().unwrap();
+    controls.insert(cid, Control::new(Answer::Detailed(Detailed::Fully), None));
+    let mut cmm = SOCData::new(controls, None, IndexMap::new());
+    cmm.set_policy(Policy::new(vec![PolicyRule::new(5, false, 1)]));
+
+    assert_eq!(cmm.violations().len(), 1);
+    assert!(cmm.finalize().is_err());
+    assert!(!cmm.finalized());
+
+    cmm.add_evidence(&cid, "SOC charter v1.2");
+    assert!(cmm.violations().is_empty());
+    assert!(cmm.finalize().is_ok());
+    assert!(cmm.finalized());
+
+    let toml = toml::to_string(&cmm).unwrap();
+    assert_eq!(toml::from_str::<SOCData>(&toml).unwrap(), cmm);
+
+    // Editing a finalized assessment must not leave it finalized with violations
+    cmm.remove_evidence(&cid, "SOC charter v1.2");
+    assert_eq!(cmm.violations().len(), 1);
+    assert!(!cmm.finalized());
+    // Level 3 needs no evidence, the policy is met again
+    cmm.set_answer(&cid, Answer::Detailed(Detailed::Partially));
+    assert!(cmm.finalized());
+}
+
+#[test]
+fn test_narratives() {
+    let mut cmm = SOCData::new(IndexMap::new(), None, IndexMap::new());
+    let aspect: CID = "People.2".parse().unwrap();
+
+    cmm.set_aspect_narrative(
+        &aspect,
+        NarrativeSection::Strengths,
+        Some(String::from(
+            "- Clear role hierarchy\n- Documented responsibilities",
+        )),
+    );
+    cmm.set_domain_narrative(
+        &Domain::People,
+        NarrativeSection::Findings,
+        Some(String::from("Knowledge management is **ad-hoc**")),
+    );
+    assert_eq!(
+        cmm.aspect_narrative(&aspect)
+            .and_then(|narrative| narrative.get(NarrativeSection::Strengths)),
+        Some(&String::from(
+            "- Clear role hierarchy\n- Documented responsibilities"
+        ))
+    );
+
+    let toml = toml::to_string(&cmm).unwrap();
+    assert_eq!(toml::from_str::<SOCData>(&toml).unwrap(), cmm);
+
+    cmm.set_aspect_narrative(
+        &aspect,
+        NarrativeSection::Strengths,
+        Some(String::from(" ")),
+    );
+    assert!(cmm.aspect_narrative(&aspect).is_none());
+}
@@ -0,0 +1,195 @@
+use cmm_core::{
+    action::{Action, ActionStatus},
+    cid::CID,
+};
+use dioxus::prelude::*;
+use strum::VariantArray;
+
+use crate::{
+    components::{SmallButtonComponent, ValueOrPlaceholderComponent},
+    utils::{use_schema, use_soc_data},
+};
+
+#[component]
+pub fn ActionEditorComponent(cid: CID, action: Option<Action>) -> Element {
+    let mut data = use_soc_data();
+
+    let Some(action) = action else {
+        return rsx! {
+            div {
+                class: "mt-4 print:hidden",
+                SmallButtonComponent {
+                    onclick: move |_| {
+                        data.write().set_action(&cid, Some(Action::default()));
+                    },
+                    "Add improvement action"
+                }
+            }
+        };
+    };
+
+    let class = "dark:bg-slate-700 bg-slate-200 not-dark:border-1 not-dark:border-slate-300 rounded px-2 py-1.5 w-full";
+
+    rsx! {
+        div {
+            class: "mt-4 grid gap-2 md:grid-cols-3",
+            span {
+                class: "text-sm md:col-span-3 flex justify-between",
+                "Improvement action"
+                SmallButtonComponent {
+                    onclick: move |_| {
+                        data.write().set_action(&cid, None);
+                    },
+                    "Remove"
+                }
+            }
+            label {
+                class: "text-xs grid gap-1",
+                "Owner"
+                input {
+                    class,
+                    r#type: "text",
+                    value: action.owner(),
+                    onchange: {
+                        let action = action.clone();
+                        move |evt: FormEvent| {
+                            let mut action = action.clone();
+                            action.set_owner(evt.value());
+                            data.write().set_action(&cid, Some(action));
+                        }
+                    },
+                }
+            }
+            label {
+                class: "text-xs grid gap-1",
+                "Due date"
+                input {
+                    class,
+                    r#type: "date",
+                    value: action.due().map(ToString::to_string).unwrap_or_default(),
+                    onchange: {
+                        let action = action.clone();
+                        move |evt: FormEvent| {
+                            let mut action = action.clone();
+                            // Cleared or incomplete dates remove the due date
+                            action.set_due(evt.value().parse().ok());
+                            data.write().set_action(&cid, Some(action));
+                        }
+                    },
+                }
+            }
+            label {
+                class: "text-xs grid gap-1",
+                "Status"
+                select {
+                    class: "{class} cursor-pointer",
+                    onchange: {
+                        let action = action.clone();
+                        move |evt: FormEvent| {
+                            let mut action = action.clone();
+                            action.set_status(evt.value().parse().unwrap_or_default());
+                            data.write().set_action(&cid, Some(action));
+                        }
+                    },
+                    for status in ActionStatus::VARIANTS {
+                        option {
+                            value: "{status:?}",
+                            selected: *status == action.status(),
+                            "{status}"
+                        }
+                    }
+                }
+            }
+            label {
+                class: "text-xs grid gap-1 md:col-span-3",
+                "Description"
+                textarea {
+                    class,
+                    value: action.description(),
+                    onchange: {
+                        let action = action.clone();
+                        move |evt: FormEvent| {
+                            let mut action = action.clone();
+                            action.set_description(evt.value());
+                            data.write().set_action(&cid, Some(action));
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn ActionPlanComponent() -> Element {
+    let data = use_soc_data();
+    let schema = use_schema();
+
+    if data().action_plan().is_empty() {
+        return rsx! {
+            div {
+                class: "opacity-60",
+                "No improvement actions yet. Open a control and click \"Add improvement action\"."
+            }
+        };
+    }
+
+    rsx! {
+        table {
+            class: "w-full text-left border-collapse text-sm",
+            thead {
+                tr {
+                    class: "text-xs opacity-70",
+                    th { class: "py-1 pr-2 font-medium", "Due" }
+                    th { class: "py-1 pr-2 font-medium", "Control" }
+                    th { class: "py-1 pr-2 font-medium", "Action" }
+                    th { class: "py-1 pr-2 font-medium", "Owner" }
+                    th { class: "py-1 pr-2 font-medium", "Status" }
+                }
+            }
+            tbody {
+                for (cid , action) in data().action_plan() {
+                    tr {
+                        key: "{cid}",
+                        class: "border-t border-slate-300 dark:border-slate-700 align-top",
+                        class: if action.status() == ActionStatus::Done { "opacity-60" },
+                        td {
+                            class: "py-1 pr-2 whitespace-nowrap",
+                            ValueOrPlaceholderComponent {
+                                value: action.due().map(ToString::to_string).unwrap_or_default(),
+                            }
+                        }
+                        td {
+                            class: "py-1 pr-2",
+                            a {
+                                class: "hover:underline",
+                                href: "./#{cid}",
+                                "{cid}"
+                            }
+                            div {
+                                class: "text-xs opacity-70",
+                                "{schema.control_schema(cid).map(|schema| schema.title().clone()).unwrap_or_default()}"
+                            }
+                        }
+                        td {
+                            class: "py-1 pr-2 whitespace-pre-wrap",
+                            ValueOrPlaceholderComponent {
+                                value: "{action.description()}",
+                            }
+                        }
+                        td {
+                            class: "py-1 pr-2",
+                            ValueOrPlaceholderComponent {
+                                value: "{action.owner()}",
+                            }
+                        }
+                        td {
+                            class: "py-1 pr-2 whitespace-nowrap",
+                            "{action.status()}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use cmm_core::{cid::CID, control::Comment};
+use dioxus::prelude::*;
+use dioxus_free_icons::{
+    Icon,
+    icons::fa_solid_icons::{FaCheck, FaTrash},
+};
+
+use crate::{
+    components::SmallButtonComponent,
+    utils::{use_app_settings, use_soc_data},
+};
+
+#[component]
+pub fn CommentThreadComponent(cid: CID, comments: Vec<Comment>) -> Element {
+    let mut data = use_soc_data();
+    let settings = use_app_settings();
+
+    let mut draft = use_signal(String::new);
+
+    rsx! {
+        div {
+            class: "mt-4 grid gap-2",
+            span {
+                class: "text-sm",
+                "Discussion"
+            }
+            for (i , comment) in comments.iter().enumerate() {
+                div {
+                    key: "{cid}_{i}_{comment.resolved()}",
+                    class: "rounded py-2 px-3 dark:bg-slate-900 bg-slate-200 border-l-4",
+                    class: if comment.resolved() { "border-green-500 opacity-60" } else { "border-blue-400" },
+                    div {
+                        class: "flex justify-between items-center gap-2 text-xs mb-1",
+                        span {
+                            span {
+                                class: "font-semibold",
+                                "{comment.author()}"
+                            }
+                            span {
+                                class: "opacity-70 ml-2",
+                                "{comment.timestamp()}"
+                            }
+                        }
+                        div {
+                            class: "flex gap-2 print:hidden",
+                            SmallButtonComponent {
+                                onclick: move |_| {
+                                    data.write().toggle_comment_resolved(&cid, i);
+                                },
+                                Icon {
+                                    width: 12,
+                                    height: 12,
+                                    fill: "white",
+                                    icon: FaCheck,
+                                }
+                                if comment.resolved() {
+                                    "Reopen"
+                                } else {
+                                    "Resolve"
+                                }
+                            }
+                            SmallButtonComponent {
+                                onclick: move |_| {
+                                    data.write().remove_comment(&cid, i);
+                                },
+                                Icon {
+                                    width: 12,
+                                    height: 12,
+                                    fill: "white",
+                                    icon: FaTrash,
+                                }
+                            }
+                        }
+                    }
+                    p {
+                        class: "whitespace-pre-wrap",
+                        "{comment.text()}"
+                    }
+                }
+            }
+            div {
+                class: "flex gap-2 items-end print:hidden",
+                textarea {
+                    class: "dark:bg-slate-700 bg-slate-200 not-dark:border-1 not-dark:border-slate-300 rounded px-2 py-1.5 w-full",
+                    placeholder: if settings().author.is_empty() { "Reply (set your name in the settings)" } else { "Reply as {settings().author}" },
+                    value: draft(),
+                    oninput: move |evt| {
+                        draft.set(evt.value());
+                    },
+                }
+                SmallButtonComponent {
+                    onclick: move |_| {
+                        if draft().trim().is_empty() {
+                            return;
+                        }
+                        let timestamp: String = web_sys::js_sys::Date::new_0().to_iso_string().into();
+                        data.write()
+                            .add_comment(
+                                &cid,
+                                Comment::new(settings().author, timestamp, draft().trim().to_owned()),
+                            );
+                        draft.set(String::new());
+                    },
+                    "Send"
+                }
+            }
+        }
+    }
+}
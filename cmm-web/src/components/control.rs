@@ -2,27 +2,36 @@ use cmm_core::{
     answer::Answer,
     cid::{CID, Domain},
     control::Control,
+    lint::{Finding, Lint},
     schema::{ControlSchema, ControlType},
 };
 use dioxus::prelude::*;
 use dioxus_free_icons::{
     Icon,
-    icons::fa_solid_icons::{FaArrowRightLong, FaCircleInfo},
+    icons::fa_solid_icons::{FaArrowRightLong, FaCircleInfo, FaCircleExclamation, FaComment, FaTriangleExclamation},
 };
 use strum::VariantArray;
 
 use crate::{
     components::{
-        CompletenessScoreComponent, DomainIconComponent, SmallButtonComponent, StarButtonComponent,
+        ActionEditorComponent, CommentThreadComponent, CompletenessScoreComponent,
+        DomainIconComponent, EvidenceEditorComponent, NarrativeEditorComponent,
+        ScopeButtonComponent, SmallButtonComponent, StarButtonComponent, TagEditorComponent,
         ValueOrPlaceholderComponent,
     },
-    utils::{use_app_settings, use_schema, use_soc_compare_data, use_soc_data},
+    utils::{
+        use_app_settings, use_dependencies, use_lint, use_schema, use_soc_compare_data,
+        use_soc_data,
+    },
 };
 
+/// `pinned` only lists bookmarked controls and `tag` only the controls with that tag,
+/// filtered lists are shown without the domain and aspect headings
 #[component]
-pub fn ControlsListComponent(pinned: bool) -> Element {
+pub fn ControlsListComponent(pinned: bool, tag: Option<String>) -> Element {
     let schema = use_schema();
     let data = use_soc_data();
+    let filtered = pinned || tag.is_some();
 
     let indent_list = |controls: Vec<(&CID, &ControlSchema)>| -> Vec<Vec<(CID, ControlSchema)>> {
         let mut output: Vec<Vec<(CID, ControlSchema)>> = vec![];
@@ -36,7 +45,7 @@ pub fn ControlsListComponent(pinned: bool) -> Element {
                 current_list.clear();
             }
             // By filtering out unused controls, we prevent a lag spike :D
-            if pinned && let Some(ctrl) = data().control(cid) && !ctrl.bookmark() {
+            if filtered && let Some(ctrl) = data().control(cid) && !in_filter(ctrl, pinned, &tag) {
                 continue;
             }
             current_list.push((*cid, control.clone()));
@@ -45,7 +54,16 @@ pub fn ControlsListComponent(pinned: bool) -> Element {
         output
     };
 
-    if pinned && !data().has_pinned_items() {
+    if let Some(tag) = &tag {
+        if data().controls_by_tag(tag).count() == 0 {
+            return rsx! {
+                div {
+                    class: "opacity-60",
+                    "No controls tagged with \"{tag}\"."
+                }
+            };
+        }
+    } else if pinned && !data().has_pinned_items() {
         return rsx!{
             div {
                 class: "opacity-60",
@@ -63,7 +81,7 @@ pub fn ControlsListComponent(pinned: bool) -> Element {
 
     rsx! {
         for domain in Domain::VARIANTS {
-            if !pinned {
+            if !filtered {
                 h3 {
                     class: "text-3xl mb-2 mt-6 font-semibold flex items-center gap-2",
                     id: "variant-{domain}",
@@ -78,15 +96,28 @@ pub fn ControlsListComponent(pinned: bool) -> Element {
                     }
                     "{domain}"
                 }
+                NarrativeEditorComponent {
+                    domain: *domain,
+                }
             }
             div {
 
                 for (i , aspect) in schema.aspects(domain).iter().enumerate() {
-                    if !pinned {
+                    if !filtered {
                         h4 {
-                            class: "text-2xl mb-2 mt-6 font-semibold",
+                            class: "text-2xl mb-2 mt-6 font-semibold flex justify-between items-center",
                             id: "aspect-{domain}-{i + 1}",
-                            "{i + 1}. {aspect}"
+                            span {
+                                class: if !data().in_scope(&aspect_cid(domain, i)) { "opacity-40" },
+                                "{i + 1}. {aspect}"
+                            }
+                            ScopeButtonComponent {
+                                cid: aspect_cid(domain, i),
+                            }
+                        }
+                        NarrativeEditorComponent {
+                            domain: *domain,
+                            aspect: aspect_cid(domain, i),
                         }
                     }
                     div {
@@ -97,10 +128,11 @@ pub fn ControlsListComponent(pinned: bool) -> Element {
 
                                     for (cid , _) in indent_items {
                                         ControlItemComponent {
-                                            key: "{cid}_{pinned}",
+                                            key: "{cid}_{pinned}_{tag:?}",
                                             cid: cid.to_owned(),
                                             control_option: data().control(&cid).cloned(),
                                             pinned,
+                                            tag: tag.clone(),
                                         }
                                     }
                                 }
@@ -113,10 +145,20 @@ pub fn ControlsListComponent(pinned: bool) -> Element {
     }
 }
 
+fn aspect_cid(domain: &Domain, index: usize) -> CID {
+    format!("{domain}.{}", index + 1).parse().unwrap()
+}
+
+/// Filtered lists show bookmarked controls and/or the controls with the given tag
+fn in_filter(control: &Control, pinned: bool, tag: &Option<String>) -> bool {
+    (!pinned || control.bookmark()) && tag.as_ref().is_none_or(|tag| control.has_tag(tag))
+}
+
 #[component]
 fn ControlItemComponent(
     cid: CID,
     pinned: bool,
+    tag: Option<String>,
     // we need to pass this for reactivity to work as intented
     control_option: Option<Control>,
 ) -> Element {
@@ -124,13 +166,16 @@ fn ControlItemComponent(
     let schema = use_schema();
     let compare_data = use_soc_compare_data();
     let settings = use_app_settings();
+    let lint = use_lint();
+    let dependencies = use_dependencies();
 
     let ctrl_schema = schema.control_schema(&cid).unwrap().clone();
+    let filtered = pinned || tag.is_some();
 
     let indent = cid.indent() - 1;
 
     let Some(control) = control_option else {
-        if pinned {
+        if filtered {
             return rsx!();
         }
 
@@ -140,14 +185,21 @@ fn ControlItemComponent(
                 class: if indent > 1 { "text-lg" } else { "text-xl" },
                 id: "{cid}",
                 span {
+                    class: if !data().in_scope(&cid) { "opacity-40" },
                     "{cid.as_short_string()} {ctrl_schema.title()}"
                 }
-                if ctrl_schema.control_type() == &ControlType::ScoredSectionTitle {
-                    SmallButtonComponent {
-                        CompletenessScoreComponent {
-                            score: data().section_completeness(&cid)
+                div {
+                    class: "flex gap-2 items-center",
+                    if ctrl_schema.control_type() == &ControlType::ScoredSectionTitle {
+                        SmallButtonComponent {
+                            CompletenessScoreComponent {
+                                score: data().section_completeness(&cid)
+                            }
                         }
                     }
+                    ScopeButtonComponent {
+                        cid,
+                    }
                 }
             }
             if ctrl_schema.remarks().is_some() {
@@ -159,7 +211,7 @@ fn ControlItemComponent(
         };
     };
 
-    if pinned && !control.bookmark() {
+    if filtered && !in_filter(&control, pinned, &tag) {
         return rsx!();
     }
 
@@ -205,6 +257,49 @@ fn ControlItemComponent(
         };
     }
 
+    // Dependencies of a custom schema always apply, the opt-in ones only if enabled
+    let dependency = ctrl_schema
+        .depends_on()
+        .or_else(|| {
+            settings()
+                .conditional_controls
+                .then(|| dependencies.dependency(&cid))
+                .flatten()
+        })
+        .filter(|dependency| !dependency.is_met(&data()))
+        .cloned();
+
+    // Dependent controls without a cap are not applicable, so they are collapsed
+    if let Some(dependency) = &dependency
+        && dependency.cap().is_none()
+    {
+        return rsx! {
+            div {
+                class: "indent-{indent} pt-1 pb-0.5",
+                div {
+                    class: "dark:bg-slate-800 bg-slate-100 border-1 dark:border-slate-700 border-slate-300 rounded p-3 flex justify-between opacity-50",
+                    span {
+                        span {
+                            class: "opacity-70 mr-2",
+                            "{cid.as_short_string()}"
+                        }
+                        "{ctrl_schema.title()}"
+                    }
+                    a {
+                        class: "text-sm underline",
+                        href: "#{dependency.parent()}",
+                        "Not applicable, depends on {dependency.parent().as_short_string()}"
+                    }
+                }
+            }
+        };
+    }
+
+    let findings: Vec<Finding> = Lint::findings_for(&lint.check(&data()), &cid)
+        .cloned()
+        .collect();
+    let violations = data().policy().check(&cid, &control);
+
     let show_comparison = |cid: &CID| -> bool {
         let Some(cmp_ctrl) = compare_data().control(&cid).cloned() else {
             return false;
@@ -215,6 +310,7 @@ fn ControlItemComponent(
     rsx! {
         div {
             class: "indent-{indent} pt-1 pb-0.5 relative",
+            class: if !data().in_scope(&cid) { "opacity-40" },
             tabindex: "-1",
             details {
                 class: "dark:bg-slate-800 bg-slate-100 border-1 dark:border-slate-700 border-slate-300 open:p-3 rounded dark:text-slate-50 text-slate-950 dark:not-open:hover:bg-slate-700 dark:not-open:hover:border-slate-600 not-open:hover:bg-slate-200 duration-100ms ease-in-out group group/details",
@@ -223,7 +319,7 @@ fn ControlItemComponent(
                     class: "not-in-open:p-3 cursor-pointer flex justify-between w-full",
                     span {
 
-                        if pinned {
+                        if filtered {
                             "{cid.domain()} > "
                         }
                         span {
@@ -237,6 +333,42 @@ fn ControlItemComponent(
                         div {
                             key: "{cid}_{control.bookmark()}_{control.answer()}",
                             class: "flex gap-2 items-center",
+                            if !violations.is_empty() {
+                                span {
+                                    class: "text-red-500 text-xs flex items-center gap-1",
+                                    title: "Policy violations",
+                                    Icon {
+                                        icon: FaCircleExclamation,
+                                        width: 12,
+                                        height: 12,
+                                    }
+                                    "{violations.len()}"
+                                }
+                            }
+                            if !findings.is_empty() {
+                                span {
+                                    class: "text-amber-500 text-xs flex items-center gap-1",
+                                    title: "Inconsistent answers",
+                                    Icon {
+                                        icon: FaTriangleExclamation,
+                                        width: 12,
+                                        height: 12,
+                                    }
+                                    "{findings.len()}"
+                                }
+                            }
+                            if control.open_comments() > 0 {
+                                span {
+                                    class: "opacity-70 text-xs flex items-center gap-1",
+                                    title: "Open comments",
+                                    Icon {
+                                        icon: FaComment,
+                                        width: 12,
+                                        height: 12,
+                                    }
+                                    "{control.open_comments()}"
+                                }
+                            }
                             StarButtonComponent {
                                 onclick: move |_| {
                                     data.write().toggle_bookmark(&cid);
@@ -267,6 +399,38 @@ fn ControlItemComponent(
                         }
                     }
                 }
+                for violation in violations.iter() {
+                    div {
+                        key: "{violation.message()}",
+                        class: "mt-4 rounded py-2 px-3 border-1 border-red-500 dark:bg-slate-900 bg-slate-200",
+                        "{violation.message()}"
+                    }
+                }
+                for finding in findings.clone() {
+                    div {
+                        key: "{finding.related()}",
+                        class: "mt-4 rounded py-2 px-3 border-1 border-amber-500 dark:bg-slate-900 bg-slate-200",
+                        "{finding.message()} ("
+                        a {
+                            class: "underline",
+                            href: "#{finding.related()}",
+                            "{finding.related().as_short_string()}"
+                        }
+                        ")"
+                    }
+                }
+                if let Some(dependency) = &dependency {
+                    div {
+                        class: "mt-4 opacity-90 rounded py-2 px-3 dark:bg-slate-900 bg-slate-200",
+                        "Capped at level {dependency.cap().unwrap_or_default()} while "
+                        a {
+                            class: "underline",
+                            href: "#{dependency.parent()}",
+                            "{dependency.parent().as_short_string()}"
+                        }
+                        " is answered negatively"
+                    }
+                }
                 if ctrl_schema.remarks().is_some() {
                     div {
                         class: "mt-4 opacity-90 rounded py-2 px-3 dark:bg-slate-900 bg-slate-200 flex gap-x-2",
@@ -293,7 +457,7 @@ fn ControlItemComponent(
                             cid,
                             control: control.clone(),
                             control_schema: ctrl_schema.clone(),
-                            pinned,
+                            list: format!("{pinned}{}", tag.clone().unwrap_or_default()),
                         }
                     }
                     label {
@@ -301,9 +465,16 @@ fn ControlItemComponent(
                         span {
                             class: "text-sm mb-1",
                             "Comment"
+                            if control.missing_justification() {
+                                span {
+                                    class: "text-red-500 ml-2",
+                                    "Justification required for Not Applicable"
+                                }
+                            }
                         }
                         textarea {
                             class: "dark:bg-slate-700 bg-slate-200 not-dark:border-1 not-dark:border-slate-300 rounded px-2 py-1.5 w-full flex-1",
+                            class: if control.missing_justification() { "outline-1 outline-red-500" },
                             value: control.comment().clone().unwrap_or(String::new()),
                             onchange: move |evt| {
                                 data.write().set_comment(&cid, Some(evt.value()));
@@ -311,6 +482,25 @@ fn ControlItemComponent(
                         }
                     }
                 }
+                ActionEditorComponent {
+                    cid,
+                    action: control.action().cloned(),
+                }
+                EvidenceEditorComponent {
+                    key: "{cid}_{control.evidence().len()}",
+                    cid,
+                    evidence: control.evidence().clone(),
+                }
+                TagEditorComponent {
+                    key: "{cid}_{control.tags().len()}",
+                    cid,
+                    tags: control.tags().clone(),
+                }
+                CommentThreadComponent {
+                    key: "{cid}_{control.comments().len()}",
+                    cid,
+                    comments: control.comments().clone(),
+                }
             }
             if show_comparison(&cid) && settings().show_comparison {
                 div {
@@ -329,7 +519,8 @@ fn ControlInputComponent(
     cid: CID,
     control: ReadSignal<Control>,
     control_schema: ControlSchema,
-    pinned: bool,
+    /// Identifies the list this input is rendered in, radio groups must be unique per list
+    list: String,
 ) -> Element {
     let mut data = use_soc_data();
 
@@ -361,7 +552,7 @@ fn ControlInputComponent(
                             class: "appearance-none opacity-0",
                             tabindex: "0",
                             r#type: "radio",
-                            name: "{cid}.{pinned}",
+                            name: "{cid}.{list}",
                             checked: content == &(value == "True"),
                             onclick: move |_| async move {
                                 data.write().set_answer(&cid, Answer::Bool(value == "True"));
@@ -386,7 +577,7 @@ fn ControlInputComponent(
                         class: "appearance-none opacity-0",
                         tabindex: "0",
                         r#type: "radio",
-                        name: "{cid}.{pinned}",
+                        name: "{cid}.{list}",
                         value: variant.to_owned(),
                         checked: control().answer().variant_eq(variant),
                         onclick: move |_evt| {
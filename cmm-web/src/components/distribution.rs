@@ -0,0 +1,65 @@
+use cmm_core::score::Distribution;
+use dioxus::prelude::*;
+
+use crate::components::{BarSegment, StackedBarComponent};
+
+const LEVEL_CLASSES: [&str; 5] = [
+    "bg-red-600",
+    "bg-orange-500",
+    "bg-yellow-400",
+    "bg-green-400",
+    "bg-green-600",
+];
+const MATURITY_LABELS: [&str; 5] = [
+    "No / Never",
+    "Partially / Sometimes",
+    "Averagely",
+    "Mostly",
+    "Fully / Always",
+];
+const CAPABILITY_LABELS: [&str; 5] = ["No", "Partially", "Averagely", "Mostly", "Fully"];
+
+/// Stacked bar of how many answers sit at each level
+#[component]
+pub fn DistributionComponent(
+    distribution: Distribution,
+    capability: bool,
+    height: Option<u32>,
+) -> Element {
+    let (levels, labels) = if capability {
+        (distribution.capability(), CAPABILITY_LABELS)
+    } else {
+        (distribution.maturity(), MATURITY_LABELS)
+    };
+
+    let mut segments: Vec<BarSegment> = levels
+        .iter()
+        .zip(labels)
+        .zip(LEVEL_CLASSES)
+        .map(|((value, label), class)| BarSegment {
+            value: *value,
+            class,
+            label: label.to_owned(),
+        })
+        .collect();
+    if capability {
+        segments.push(BarSegment {
+            value: distribution.not_required(),
+            class: "bg-slate-400",
+            label: String::from("Not Required"),
+        });
+    } else {
+        segments.push(BarSegment {
+            value: distribution.not_applicable(),
+            class: "bg-slate-400",
+            label: String::from("Not Applicable"),
+        });
+    }
+
+    rsx! {
+        StackedBarComponent {
+            segments,
+            height,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use cmm_core::cid::CID;
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::fa_solid_icons::FaXmark};
+
+use crate::utils::use_soc_data;
+
+/// References to documents or artefacts which support the answer
+#[component]
+pub fn EvidenceEditorComponent(cid: CID, evidence: Vec<String>) -> Element {
+    let mut data = use_soc_data();
+
+    rsx! {
+        div {
+            class: "mt-4 flex flex-wrap items-center gap-2",
+            span {
+                class: "text-sm",
+                "Evidence"
+            }
+            for reference in evidence {
+                span {
+                    key: "{cid}_{reference}",
+                    class: "flex items-center gap-1 bg-emerald-600 text-white py-0.5 px-2 rounded text-xs",
+                    "{reference}"
+                    button {
+                        class: "cursor-pointer print:hidden",
+                        title: "Remove evidence",
+                        onclick: move |_| {
+                            data.write().remove_evidence(&cid, &reference);
+                        },
+                        Icon {
+                            width: 10,
+                            height: 10,
+                            fill: "white",
+                            icon: FaXmark,
+                        }
+                    }
+                }
+            }
+            input {
+                class: "dark:bg-slate-700 bg-slate-200 not-dark:border-1 not-dark:border-slate-300 rounded px-2 py-0.5 text-xs print:hidden",
+                r#type: "text",
+                placeholder: "Add evidence reference",
+                onchange: move |evt| {
+                    data.write().add_evidence(&cid, &evt.value());
+                },
+            }
+        }
+    }
+}
@@ -1,23 +1,53 @@
+use std::io::Cursor;
+
+use cmm_compat::{from_ods_reader, from_xlsx_reader};
 use cmm_core::data::SOCData;
 use dioxus::prelude::*;
 use dioxus_free_icons::{Icon, icons::fa_solid_icons::FaCopy, icons::fa_solid_icons::FaDownload};
 use wasm_bindgen_futures::JsFuture;
 
-use crate::utils::{use_app_settings, use_soc_compare_data, use_soc_data};
+use crate::utils::{use_app_settings, use_schema, use_soc_compare_data, use_soc_data};
 
 #[component]
 pub fn ImportExportComponent() -> Element {
     let mut data = use_soc_data();
     let mut cmp_data = use_soc_compare_data();
     let settings = use_app_settings();
+    // In a signal so the upload handler stays Copy
+    let schema = use_schema();
+    let schema = use_signal(move || schema);
 
     let mut copied = use_signal(|| false);
+    let mut import_warnings = use_signal(Vec::<String>::new);
 
-    let upload_file_handler = async move |evt: FormEvent| -> Result<SOCData, String> {
+    // Excel and LibreOffice assessments are converted, everything which could not be mapped is listed as warning
+    let upload_file_handler = async move |evt: FormEvent| -> Result<(SOCData, Vec<String>), String> {
         let files = evt.files();
         let Some(file) = files.first() else {
             return Err(String::from("No file given"));
         };
+        let name = file.name();
+        if name.ends_with(".xlsx") || name.ends_with(".ods") {
+            let bytes = file
+                .read_bytes()
+                .await
+                .map_err(|err| format!("Could not read file: {err}"))?;
+            let imported = if name.ends_with(".ods") {
+                from_ods_reader(Cursor::new(bytes), &schema.read())
+            } else {
+                from_xlsx_reader(Cursor::new(bytes), &schema.read())
+            };
+            let (mut result, report) =
+                imported.map_err(|err| format!("Could not import workbook: {err}"))?;
+            result.sort_controls();
+            let warnings = report
+                .warnings()
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            return Ok((result, warnings));
+        }
+
         let content = file
             .read_string()
             .await
@@ -26,7 +56,7 @@ pub fn ImportExportComponent() -> Element {
         match toml::from_str::<SOCData>(&content) {
             Ok(mut result) => {
                 result.sort_controls();
-                Ok(result)
+                Ok((result, Vec::new()))
             },
             Err(err) => Err(format!("{}", err)),
         }
@@ -74,22 +104,36 @@ pub fn ImportExportComponent() -> Element {
                 label {
                     class: "text-sm mb-2 block",
                     r#for: "textreader",
-                    "Import SOC data from TOML file"
+                    "Import SOC data from TOML file or Excel/LibreOffice assessment"
                 }
                 input {
                     class: "bg-slate-700 py-1 px-2 rounded cursor-pointer hover:bg-slate-600 w-full border-1 border-slate-500",
                     r#type: "file",
-                    accept: ".toml",
+                    accept: ".toml,.xlsx,.ods",
                     multiple: false,
                     name: "textreader",
                     directory: false,
                     onchange: move |evt: FormEvent| async move {
                         match upload_file_handler(evt).await {
-                            Ok(soc) => data.set(soc),
-                            Err(err) => tracing::debug!("Could not upload soc: {}", err)
+                            Ok((soc, warnings)) => {
+                                data.set(soc);
+                                import_warnings.set(warnings);
+                            }
+                            Err(err) => {
+                                tracing::debug!("Could not upload soc: {}", err);
+                                import_warnings.set(vec![err]);
+                            }
                         }
                     },
                 }
+                if !import_warnings().is_empty() {
+                    ul {
+                        class: "mt-2 text-xs text-amber-300 list-disc list-inside max-h-40 overflow-y-auto",
+                        for warning in import_warnings() {
+                            li { "{warning}" }
+                        }
+                    }
+                }
             }
             div {
                 class: "border-1 p-4 rounded-2xl border-slate-700 bg-slate-900",
@@ -138,13 +182,13 @@ pub fn ImportExportComponent() -> Element {
                     input {
                         class: "bg-slate-700 py-1 px-2 rounded cursor-pointer hover:bg-slate-600 w-full border-1 border-slate-500",
                         r#type: "file",
-                        accept: ".toml",
+                        accept: ".toml,.xlsx,.ods",
                         multiple: false,
                         name: "textreader",
                         directory: false,
                         onchange: move |evt: FormEvent| async move {
                             match upload_file_handler(evt).await {
-                                Ok(soc) => cmp_data.set(soc),
+                                Ok((soc, _warnings)) => cmp_data.set(soc),
                                 Err(err) => tracing::debug!("Could not upload soc: {}", err)
                             }
                         }
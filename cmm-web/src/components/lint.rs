@@ -0,0 +1,47 @@
+use dioxus::prelude::*;
+
+use crate::utils::{use_lint, use_schema, use_soc_data};
+
+/// All consistency findings of the current assessment
+#[component]
+pub fn LintFindingsComponent() -> Element {
+    let data = use_soc_data();
+    let schema = use_schema();
+    let lint = use_lint();
+
+    let findings = lint.check(&data());
+
+    if findings.is_empty() {
+        return rsx! {
+            div {
+                class: "opacity-60",
+                "No inconsistent answers found."
+            }
+        };
+    }
+
+    rsx! {
+        ul {
+            class: "grid gap-2",
+            for finding in findings {
+                li {
+                    key: "{finding.cid()}_{finding.related()}",
+                    class: "rounded py-2 px-3 dark:bg-slate-800 bg-slate-100 border-1 border-amber-500",
+                    a {
+                        class: "hover:underline font-semibold",
+                        href: "#{finding.cid()}",
+                        "{finding.cid()} "
+                        span {
+                            class: "font-normal opacity-70",
+                            "{schema.control_schema(finding.cid()).map(|schema| schema.title().clone()).unwrap_or_default()}"
+                        }
+                    }
+                    div {
+                        class: "text-sm",
+                        "{finding.message()}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -10,13 +10,33 @@ mod settings;
 mod score;
 mod profile;
 mod report;
+mod comments;
+mod tags;
+mod action;
+mod search;
+mod distribution;
+mod scope;
+mod lint;
+mod evidence;
+mod policy;
+mod narrative;
 pub use control::ControlsListComponent;
 pub use overview::OverviewComponent;
 pub use sidebar::SidebarComponent;
 pub use ui::*;
 pub use chart::ChartComponent;
 pub use import_export::ImportExportComponent;
-pub use score::{ScoreComponent, SidebarScoreComponent, CompletenessScoreComponent};
+pub use score::{ScoreComponent, SidebarScoreComponent, CompletenessScoreComponent, LevelComponent};
 pub use settings::{SettingsComponent, AppSettings};
 pub use profile::{ProfileComponent};
-pub use report::{PrintOverviewComponent, ProfileValuesComponent};
+pub use report::{PrintOverviewComponent, PrintRecommendationsComponent, ProfileValuesComponent};
+pub use comments::CommentThreadComponent;
+pub use tags::{TagEditorComponent, TagFilterComponent};
+pub use action::{ActionEditorComponent, ActionPlanComponent};
+pub use search::SearchComponent;
+pub use distribution::DistributionComponent;
+pub use scope::ScopeButtonComponent;
+pub use lint::LintFindingsComponent;
+pub use evidence::EvidenceEditorComponent;
+pub use policy::PolicyComponent;
+pub use narrative::{NarrativeEditorComponent, PrintNarrativeComponent};
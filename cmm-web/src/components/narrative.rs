@@ -0,0 +1,77 @@
+use cmm_core::{
+    cid::{CID, Domain},
+    narrative::{Narrative, NarrativeSection},
+};
+use dioxus::prelude::*;
+use dioxus_markdown::Markdown;
+use strum::VariantArray;
+
+use crate::utils::use_soc_data;
+
+/// Findings, strengths and recommendations of a domain, or of an aspect if given
+#[component]
+pub fn NarrativeEditorComponent(domain: Domain, aspect: Option<CID>) -> Element {
+    let mut data = use_soc_data();
+
+    let narrative = match aspect {
+        Some(aspect) => data().aspect_narrative(&aspect).cloned(),
+        None => data().domain_narrative(&domain).cloned(),
+    }
+    .unwrap_or_default();
+
+    rsx! {
+        details {
+            class: "mb-4 rounded dark:bg-slate-800 bg-slate-100 border-1 dark:border-slate-700 border-slate-300 open:p-3 print:hidden",
+            summary {
+                class: "not-in-open:p-3 cursor-pointer text-sm",
+                if aspect.is_some() { "Aspect findings" } else { "Domain findings" }
+                if !narrative.is_empty() {
+                    span {
+                        class: "opacity-60 ml-2",
+                        "(filled in)"
+                    }
+                }
+            }
+            div {
+                class: "grid gap-2 mt-2",
+                for section in NarrativeSection::VARIANTS {
+                    label {
+                        key: "{section}",
+                        class: "grid gap-1 text-xs",
+                        "{section} (markdown)"
+                        textarea {
+                            class: "dark:bg-slate-700 bg-slate-200 not-dark:border-1 not-dark:border-slate-300 rounded px-2 py-1.5 w-full min-h-24 text-sm",
+                            value: narrative.get(*section).cloned().unwrap_or_default(),
+                            onchange: move |evt| {
+                                match aspect {
+                                    Some(aspect) => data.write().set_aspect_narrative(&aspect, *section, Some(evt.value())),
+                                    None => data.write().set_domain_narrative(&domain, *section, Some(evt.value())),
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Markdown of all filled in sections for the printed report
+#[component]
+pub fn PrintNarrativeComponent(narrative: Narrative) -> Element {
+    rsx! {
+        for section in NarrativeSection::VARIANTS {
+            if let Some(markdown) = narrative.get(*section) {
+                div {
+                    key: "{section}",
+                    class: "text-[11px] md-content break-inside-avoid",
+                    h5 {
+                        class: "font-semibold text-xs mt-2",
+                        "{section}"
+                    }
+                    Markdown { src: markdown.clone() }
+                }
+            }
+        }
+    }
+}
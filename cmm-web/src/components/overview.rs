@@ -1,6 +1,7 @@
 use crate::{
     components::{
-        BadToGoodProgressBarComponent, DomainIconComponent, ScoreComponent, SectionTitleComponent,
+        BadToGoodProgressBarComponent, DistributionComponent, DomainIconComponent, LevelComponent,
+        ScoreComponent, SectionTitleComponent,
     },
     utils::{round, use_app_settings, use_schema, use_stats},
 };
@@ -77,6 +78,12 @@ fn OverallScoreComponent(stats: ReadSignal<Stats>) -> Element {
                     " (max {round(stats.read().score_overall().max(), 1)})"
                 }
             }
+            div {
+                class: "text-slate-50 text-right font-semibold",
+                LevelComponent {
+                    score: stats.read().maturity_overall(),
+                }
+            }
         }
     }
 }
@@ -84,6 +91,7 @@ fn OverallScoreComponent(stats: ReadSignal<Stats>) -> Element {
 #[component]
 fn DomainOverviewComponent(domain: Domain, stats: ReadSignal<Stats>) -> Element {
     let schema = use_schema();
+    let settings = use_app_settings();
 
     let overall_score = stats.read().maturity_by_domain(&domain);
     let overall_capability_score = stats.read().capability_by_domain(&domain);
@@ -122,7 +130,13 @@ fn DomainOverviewComponent(domain: Domain, stats: ReadSignal<Stats>) -> Element
                             ScoreComponent {
                                 score: overall_capability_score,
                                 precision: 1,
-                                replace_nan: true
+                            }
+                            small {
+                                class: "text-[10px] opacity-80",
+                                LevelComponent {
+                                    score: overall_capability_score,
+                                    capability: true,
+                                }
                             }
                         }
                     }
@@ -137,6 +151,12 @@ fn DomainOverviewComponent(domain: Domain, stats: ReadSignal<Stats>) -> Element
                             score: overall_score,
                             precision: 1,
                         }
+                        small {
+                            class: "text-[10px] opacity-80",
+                            LevelComponent {
+                                score: overall_score,
+                            }
+                        }
                     }
                 }
             }
@@ -144,12 +164,14 @@ fn DomainOverviewComponent(domain: Domain, stats: ReadSignal<Stats>) -> Element
                 class: "mt-4 bg-slate-50 rounded-2xl p-4 border-1 border-slate-200 dark:border-slate-500 dark:bg-slate-600",
                 for (i , aspect) in schema.aspects(&domain).iter().enumerate() {
                     div {
+                        class: if !stats.read().data().in_scope(&format!("{domain}.{}", i + 1).parse().unwrap()) { "opacity-40" },
+                        title: if !stats.read().data().in_scope(&format!("{domain}.{}", i + 1).parse().unwrap()) { "Out of scope" },
                         // TODO: really ugly - why does format!() no longer work?
                         key:
-                            "{aspect}_{stats.read().maturity_by_aspect(&domain, i as u8 + 1).score()}_{stats.read().capability_by_aspect(&domain, i as u8 + 1).score()}",
+                            "{aspect}_{stats.read().maturity_by_aspect(&domain, i as u8 + 1).score():?}_{stats.read().capability_by_aspect(&domain, i as u8 + 1).score():?}",
                         span {
                             class: "text-[10px] text-right",
-                            "data-aspect-value": "{round(stats.read().maturity_by_aspect(&domain, i as u8 + 1).score(), 2)}",
+                            "data-aspect-value": "{round(stats.read().maturity_by_aspect(&domain, i as u8 + 1).score().unwrap_or_default(), 2)}",
                             "{aspect}"
                         }
                         div {
@@ -165,6 +187,16 @@ fn DomainOverviewComponent(domain: Domain, stats: ReadSignal<Stats>) -> Element
                                 score: stats.read().maturity_by_aspect(&domain, i as u8 + 1),
                                 tooltip_prefix: "{aspect} maturity: ",
                             }
+                            if settings().show_distribution {
+                                div {
+                                    class: "mt-1",
+                                    DistributionComponent {
+                                        distribution: stats.read().distribution_by_aspect(&domain, i as u8 + 1),
+                                        capability: false,
+                                        height: 1,
+                                    }
+                                }
+                            }
                             if domain == Domain::Technology || domain == Domain::Services {
                                 div {
                                     class: "mt-1",
@@ -173,6 +205,16 @@ fn DomainOverviewComponent(domain: Domain, stats: ReadSignal<Stats>) -> Element
                                         tooltip_prefix: "{aspect} capability: ",
                                     }
                                 }
+                                if settings().show_distribution {
+                                    div {
+                                        class: "mt-1",
+                                        DistributionComponent {
+                                            distribution: stats.read().distribution_by_aspect(&domain, i as u8 + 1),
+                                            capability: true,
+                                            height: 1,
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -0,0 +1,164 @@
+use cmm_core::policy::PolicyRule;
+use dioxus::prelude::*;
+
+use crate::{
+    components::SmallButtonComponent,
+    utils::{use_schema, use_soc_data},
+};
+
+/// Edit the evidence policy, list its violations and finalize the assessment
+#[component]
+pub fn PolicyComponent() -> Element {
+    let mut data = use_soc_data();
+    let schema = use_schema();
+
+    let mut at_least = use_signal(|| 4_u32);
+    let mut comment = use_signal(|| true);
+    let mut evidence = use_signal(|| 0_usize);
+
+    let violations = data().violations();
+    let missing_justifications = data().missing_justifications().count();
+    let can_finalize = violations.is_empty() && missing_justifications == 0;
+    let class = "dark:bg-slate-700 bg-slate-200 not-dark:border-1 not-dark:border-slate-300 rounded px-2 py-0.5";
+
+    rsx! {
+        div {
+            class: "grid gap-2 mb-6",
+            for (i , rule) in data().policy().rules().iter().enumerate() {
+                div {
+                    key: "{i}_{rule.at_least()}_{rule.comment()}_{rule.evidence()}",
+                    class: "flex justify-between items-center rounded py-2 px-3 dark:bg-slate-800 bg-slate-100",
+                    span {
+                        "Level {rule.at_least()} or above requires "
+                        if rule.comment() {
+                            "a comment"
+                        }
+                        if rule.comment() && rule.evidence() > 0 {
+                            " and "
+                        }
+                        if rule.evidence() > 0 {
+                            "at least {rule.evidence()} evidence reference(s)"
+                        }
+                    }
+                    SmallButtonComponent {
+                        onclick: move |_| {
+                            let mut policy = data().policy().clone();
+                            policy.remove_rule(i);
+                            data.write().set_policy(policy);
+                        },
+                        "Remove"
+                    }
+                }
+            }
+            div {
+                class: "flex flex-wrap items-center gap-2 text-sm print:hidden",
+                "Level"
+                input {
+                    class: "{class} w-14",
+                    r#type: "number",
+                    min: 1,
+                    max: 5,
+                    value: "{at_least}",
+                    onchange: move |evt| {
+                        at_least.set(evt.value().parse().unwrap_or(4).clamp(1, 5));
+                    },
+                }
+                "or above requires"
+                label {
+                    class: "flex items-center gap-1",
+                    input {
+                        r#type: "checkbox",
+                        checked: comment(),
+                        onchange: move |evt| {
+                            comment.set(evt.checked());
+                        },
+                    }
+                    "a comment"
+                }
+                "and"
+                input {
+                    class: "{class} w-14",
+                    r#type: "number",
+                    min: 0,
+                    value: "{evidence}",
+                    onchange: move |evt| {
+                        evidence.set(evt.value().parse().unwrap_or_default());
+                    },
+                }
+                "evidence reference(s)"
+                SmallButtonComponent {
+                    onclick: move |_| {
+                        if !comment() && evidence() == 0 {
+                            return;
+                        }
+                        let mut policy = data().policy().clone();
+                        policy.add_rule(PolicyRule::new(at_least(), comment(), evidence()));
+                        data.write().set_policy(policy);
+                    },
+                    "Add rule"
+                }
+            }
+        }
+        if !violations.is_empty() {
+            ul {
+                class: "grid gap-2 mb-4",
+                for violation in violations.iter() {
+                    li {
+                        key: "{violation.cid()}_{violation.message()}",
+                        class: "rounded py-2 px-3 dark:bg-slate-800 bg-slate-100 border-1 border-red-500",
+                        a {
+                            class: "hover:underline font-semibold",
+                            href: "#{violation.cid()}",
+                            "{violation.cid()} "
+                            span {
+                                class: "font-normal opacity-70",
+                                "{schema.control_schema(violation.cid()).map(|schema| schema.title().clone()).unwrap_or_default()}"
+                            }
+                        }
+                        div {
+                            class: "text-sm",
+                            "{violation.message()}"
+                        }
+                    }
+                }
+            }
+        }
+        div {
+            class: "flex items-center gap-4 print:hidden",
+            if data().finalized() {
+                span {
+                    class: "font-semibold text-green-600",
+                    "Assessment finalized"
+                }
+                SmallButtonComponent {
+                    onclick: move |_| {
+                        data.write().reopen();
+                    },
+                    "Reopen"
+                }
+            } else {
+                button {
+                    class: "rounded border-2 py-1 px-3 font-semibold",
+                    class: if can_finalize { "cursor-pointer hover:bg-green-500 border-green-500" } else { "opacity-50 cursor-not-allowed border-slate-400" },
+                    disabled: !can_finalize,
+                    onclick: move |_| {
+                        let _ = data.write().finalize();
+                    },
+                    "Finalize assessment"
+                }
+                if !violations.is_empty() {
+                    span {
+                        class: "text-sm text-red-500",
+                        "{violations.len()} policy violation(s) remain"
+                    }
+                }
+                if missing_justifications > 0 {
+                    span {
+                        class: "text-sm text-red-500",
+                        "{missing_justifications} not applicable answer(s) lack a justification"
+                    }
+                }
+            }
+        }
+    }
+}
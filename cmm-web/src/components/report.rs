@@ -1,6 +1,6 @@
 use cmm_core::{cid::Domain, profile::QuestionCategory};
 use dioxus::prelude::*;
-use crate::{components::{PrintBoxComponent, ScoreComponent, ValueOrPlaceholderComponent}, utils::use_soc_data};
+use crate::{components::{DistributionComponent, LevelComponent, PrintBoxComponent, PrintNarrativeComponent, ScoreComponent, ValueOrPlaceholderComponent}, utils::use_soc_data};
 use strum::VariantArray;
 
 use crate::utils::{use_schema, use_stats};
@@ -56,6 +56,7 @@ pub fn PrintValueHolderBoxComponent(children: Element) -> Element {
 #[component]
 pub fn PrintOverviewComponent() -> Element {
     let schema = use_schema();
+    let data = use_soc_data();
     let (stats, _) = use_stats();
 
     rsx! {
@@ -71,8 +72,12 @@ pub fn PrintOverviewComponent() -> Element {
                                 ScoreComponent {
                                     score: stats.read().maturity_by_domain(&domain),
                                     precision: 2,
-                                    replace_nan: true
                                 }
+                                " ("
+                                LevelComponent {
+                                    score: stats.read().maturity_by_domain(&domain),
+                                }
+                                ")"
                             }
                             if domain == &Domain::Technology || domain == &Domain::Services {
                                 span {
@@ -81,7 +86,6 @@ pub fn PrintOverviewComponent() -> Element {
                                     ScoreComponent {
                                         score: stats.read().capability_by_domain(&domain),
                                         precision: 2,
-                                        replace_nan: true
                                     }
                                 }
                             }
@@ -101,12 +105,20 @@ pub fn PrintOverviewComponent() -> Element {
                                 class: "py-1 pr-2 font-medium text-center",
                                 "Maturity"
                             }
+                            th {
+                                class: "py-1 pr-2 font-medium",
+                                "Level"
+                            }
                             if domain == &Domain::Technology || domain == &Domain::Services {
                                 th {
                                     class: "py-1 pr-2 font-medium text-center",
                                     "Capability"
                                 }
                             }
+                            th {
+                                class: "py-1 font-medium text-center",
+                                "Distribution"
+                            }
                         }
                     }
                     tbody {
@@ -122,30 +134,131 @@ pub fn PrintOverviewComponent() -> Element {
                                     class: "text-[11px] py-[6px] w-[52px] pr-2 text-right",
                                     PrintValueHolderBoxComponent {
                                         ScoreComponent {
-                                            score: stats.read().maturity_by_aspect(&domain, i as u8),
+                                            score: stats.read().maturity_by_aspect(&domain, i as u8 + 1),
                                             precision: 2,
-                                            replace_nan: true
                                         }
                                     }
                                 }
+                                td {
+                                    class: "text-[10px] pr-2 whitespace-nowrap",
+                                    LevelComponent {
+                                        score: stats.read().maturity_by_aspect(&domain, i as u8 + 1),
+                                    }
+                                }
                                 if domain == &Domain::Technology || domain == &Domain::Services {
                                     td {
                                         class: "text-[11px] w-[52px] pr-2 text-right",
                                         PrintValueHolderBoxComponent {
                                             ScoreComponent {
-                                                score: stats.read().capability_by_aspect(&domain, i as u8),
+                                                score: stats.read().capability_by_aspect(&domain, i as u8 + 1),
                                                 precision: 2,
-                                                replace_nan: true
                                             }
                                         }
                                     }
                                 }
+                                td {
+                                    class: "w-[120px] py-[6px]",
+                                    DistributionComponent {
+                                        distribution: stats.read().distribution_by_aspect(&domain, i as u8 + 1),
+                                        capability: false,
+                                    }
+                                    if domain == &Domain::Technology || domain == &Domain::Services {
+                                        div {
+                                            class: "mt-1",
+                                            DistributionComponent {
+                                                distribution: stats.read().distribution_by_aspect(&domain, i as u8 + 1),
+                                                capability: true,
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some(narrative) = data().aspect_narrative(&format!("{domain}.{}", i + 1).parse().unwrap()) {
+                                tr {
+                                    key: "{aspect}_narrative",
+                                    td {
+                                        colspan: 5,
+                                        class: "pb-2",
+                                        PrintNarrativeComponent {
+                                            narrative: narrative.clone(),
+                                        }
+                                    }
+                                }
                             }
                         } 
                     }
                 }
+                if let Some(narrative) = data().domain_narrative(domain) {
+                    div {
+                        class: "mt-2 border-t border-slate-200",
+                        PrintNarrativeComponent {
+                            narrative: narrative.clone(),
+                        }
+                    }
+                }
             }
         }
     }
 }
 
+
+/// Prioritised roadmap, the controls at the lowest levels come first
+#[component]
+pub fn PrintRecommendationsComponent() -> Element {
+    let schema = use_schema();
+    let (stats, _) = use_stats();
+
+    let recommendations = stats.read().recommendations();
+
+    if recommendations.is_empty() {
+        return rsx! {
+            div {
+                class: "opacity-60",
+                "No recommendations, all controls with a recommendation are at the highest level."
+            }
+        };
+    }
+
+    rsx! {
+        table {
+            class: "w-full text-left border-collapse",
+            thead {
+                tr {
+                    class: "text-[10px] text-slate-500",
+                    th { class: "py-1 pr-2 font-medium", "#" }
+                    th { class: "py-1 pr-2 font-medium", "Control" }
+                    th { class: "py-1 pr-2 font-medium text-center", "Level" }
+                    th { class: "py-1 font-medium", "Recommendation" }
+                }
+            }
+            tbody {
+                for (i, recommendation) in recommendations.iter().enumerate() {
+                    tr {
+                        key: "{recommendation.cid()}",
+                        class: "border-t border-slate-200 align-top break-inside-avoid",
+                        td {
+                            class: "text-[11px] py-[4px] pr-2",
+                            "{i + 1}"
+                        }
+                        td {
+                            class: "text-[11px] py-[4px] pr-2",
+                            "{recommendation.cid()}"
+                            div {
+                                class: "text-[10px] text-slate-500",
+                                "{schema.control_schema(recommendation.cid()).map(|schema| schema.title().clone()).unwrap_or_default()}"
+                            }
+                        }
+                        td {
+                            class: "text-[11px] py-[4px] pr-2 text-center whitespace-nowrap",
+                            "{recommendation.level()} → {recommendation.level() + 1}"
+                        }
+                        td {
+                            class: "text-[11px] py-[4px]",
+                            "{recommendation.text()}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
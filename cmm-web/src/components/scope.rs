@@ -0,0 +1,28 @@
+use cmm_core::cid::CID;
+use dioxus::prelude::*;
+
+use crate::{components::SmallButtonComponent, utils::use_soc_data};
+
+/// Toggles whether an aspect or section is part of the assessment
+#[component]
+pub fn ScopeButtonComponent(cid: CID) -> Element {
+    let mut data = use_soc_data();
+
+    rsx! {
+        span {
+            class: "print:hidden font-normal",
+            title: "Toggle whether {cid} is part of the assessment",
+            SmallButtonComponent {
+                onclick: move |evt: MouseEvent| {
+                    evt.prevent_default();
+                    data.write().toggle_scope(&cid);
+                },
+                if data().in_scope(&cid) {
+                    "In scope"
+                } else {
+                    "Out of scope"
+                }
+            }
+        }
+    }
+}
@@ -5,16 +5,13 @@ use crate::utils::{round, use_app_settings};
 
 #[component]
 pub fn CompletenessScoreComponent(score: Score) -> Element {
-    let label = if score.score().is_nan() || score.score() == 0.0 {
-        "Incomplete"
-    } else if score.as_percentage() < 35.0 {
-        "Partially complete"
-    } else if score.as_percentage() < 70.0 {
-        "Averagely complete"
-    } else if score.score() == score.max() {
-        "Fully complete"
-    } else {
-        "Mostly complete"
+    let label = match score.as_percentage() {
+        None => "Incomplete",
+        Some(percentage) if percentage == 0.0 => "Incomplete",
+        Some(percentage) if percentage < 35.0 => "Partially complete",
+        Some(percentage) if percentage < 70.0 => "Averagely complete",
+        Some(percentage) if percentage == 100.0 => "Fully complete",
+        Some(_) => "Mostly complete",
     };
 
     rsx!{
@@ -23,25 +20,27 @@ pub fn CompletenessScoreComponent(score: Score) -> Element {
 }
 
 #[component]
-pub fn ScoreComponent(score: Option<Score>, precision: u32, replace_nan: Option<bool>) -> Element {
+pub fn ScoreComponent(score: Option<Score>, precision: u32) -> Element {
     let settings = use_app_settings();
 
     let Some(scr) = score else {
         return rsx!();
     };
 
-    if replace_nan.is_some_and(|x| x) && round(scr.score(), precision).is_nan() {
+    let (Some(value), Some(percentage)) = (scr.score(), scr.as_percentage()) else {
         return rsx! {
-            "0",
-            if settings().show_percentage { "%" }
+            span {
+                title: "Not applicable",
+                "N/A"
+            }
         };
-    }
-    
+    };
+
     rsx! {
         if settings().show_percentage {
-            "{round(scr.as_percentage(), precision)}%"
+            "{round(percentage, precision)}%"
         } else {
-            "{round(scr.score(), precision)}"
+            "{round(value, precision)}"
         }
     }
 }
@@ -61,3 +60,28 @@ pub fn SidebarScoreComponent(score: Option<Score>) -> Element {
         }
     )
 }
+
+/// Named level of a score, e.g. "Level 2 – Managed"
+#[component]
+pub fn LevelComponent(score: Score, capability: Option<bool>) -> Element {
+    let settings = use_app_settings();
+    let thresholds = settings().level_thresholds;
+
+    let level = if capability.unwrap_or(false) {
+        thresholds
+            .capability_level(&score)
+            .map(|level| (level.level(), level.to_string()))
+    } else {
+        thresholds
+            .maturity_level(&score)
+            .map(|level| (level.level(), level.to_string()))
+    };
+
+    let Some((level, label)) = level else {
+        return rsx!();
+    };
+
+    rsx! {
+        "Level {level} – {label}"
+    }
+}
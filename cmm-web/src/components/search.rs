@@ -0,0 +1,105 @@
+use cmm_core::search::{SearchIndex, SearchTarget};
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::fa_solid_icons::FaMagnifyingGlass};
+
+use crate::utils::{use_schema, use_soc_data};
+
+const MAX_RESULTS: usize = 20;
+
+#[component]
+pub fn SearchComponent(onnavigate: Option<EventHandler<()>>) -> Element {
+    let schema = use_schema();
+    let data = use_soc_data();
+
+    let mut query = use_signal(String::new);
+    let index = use_memo({
+        let schema = schema.clone();
+        move || SearchIndex::new(&schema, &data())
+    });
+
+    let title = move |target: &SearchTarget| -> String {
+        match target {
+            SearchTarget::Control(cid) => format!(
+                "{} {}",
+                cid,
+                schema
+                    .control_schema(cid)
+                    .map(|control_schema| control_schema.title().clone())
+                    .unwrap_or_default()
+            ),
+            SearchTarget::Aspect(domain, aspect_id) => format!(
+                "{domain} > {}",
+                schema
+                    .aspects(domain)
+                    .get(*aspect_id as usize - 1)
+                    .map(|aspect| aspect.to_string())
+                    .unwrap_or_default()
+            ),
+        }
+    };
+
+    let results = index
+        .read()
+        .search(&query())
+        .into_iter()
+        .take(MAX_RESULTS)
+        .collect::<Vec<_>>();
+
+    rsx! {
+        div {
+            class: "mb-4",
+            label {
+                class: "flex items-center gap-2 dark:bg-slate-800 bg-slate-100 border-1 dark:border-slate-700 border-slate-300 rounded px-2 py-1.5",
+                Icon {
+                    width: 14,
+                    height: 14,
+                    fill: "currentColor",
+                    icon: FaMagnifyingGlass,
+                }
+                input {
+                    class: "bg-transparent outline-none w-full",
+                    r#type: "search",
+                    placeholder: "Search",
+                    value: query(),
+                    oninput: move |evt| {
+                        query.set(evt.value());
+                    },
+                }
+            }
+            if !query().trim().is_empty() {
+                ol {
+                    class: "mt-2 grid gap-1",
+                    if results.is_empty() {
+                        li {
+                            class: "text-sm opacity-70 px-2",
+                            "No results"
+                        }
+                    }
+                    for result in results {
+                        li {
+                            key: "{result.target().anchor()}",
+                            a {
+                                class: "block rounded px-2 py-1 dark:hover:bg-slate-800 hover:bg-slate-100",
+                                href: "#{result.target().anchor()}",
+                                onclick: move |_| {
+                                    query.set(String::new());
+                                    if let Some(onnavigate) = onnavigate {
+                                        onnavigate.call(());
+                                    }
+                                },
+                                span {
+                                    class: "text-sm block",
+                                    "{title(result.target())}"
+                                }
+                                span {
+                                    class: "text-xs block opacity-70 truncate",
+                                    "{result.field()}: {result.text()}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
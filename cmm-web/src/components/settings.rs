@@ -1,14 +1,35 @@
+use cmm_core::{
+    cid::Domain,
+    level::LevelThresholds,
+    scoring::{ScoringModelKind, WeightedDomains},
+};
+use strum::VariantArray;
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::{components::ToggleComponent, utils::use_app_settings};
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
 pub struct AppSettings {
     pub darkmode: bool,
     pub show_percentage: bool,
     pub show_scores: bool,
     pub show_comparison: bool,
+    #[serde(default)]
+    pub show_distribution: bool,
+    /// Name used as author for new comments
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub level_thresholds: LevelThresholds,
+    #[serde(default)]
+    pub scoring_model: ScoringModelKind,
+    /// Used by the weighted domains scoring model
+    #[serde(default)]
+    pub domain_weights: WeightedDomains,
+    /// Apply the opt-in dependencies between controls, the workbook scores without them
+    #[serde(default)]
+    pub conditional_controls: bool,
 }
 
 #[component]
@@ -52,5 +73,145 @@ pub fn SettingsComponent() -> Element {
             },
             label: "Show Comparison",
         }
+        ToggleComponent {
+            checked: settings().show_distribution,
+            onclick: move |_| {
+                settings.write().show_distribution = !settings().show_distribution;
+            },
+            label: "Show Distribution",
+        }
+        ToggleComponent {
+            checked: settings().conditional_controls,
+            onclick: move |_| {
+                settings.write().conditional_controls = !settings().conditional_controls;
+            },
+            label: "Conditional Controls",
+        }
+        label {
+            class: "text-sm font-medium dark:text-slate-50 text-slate-950 grid gap-1",
+            "Your name"
+            input {
+                class: "dark:bg-slate-700 bg-slate-200 not-dark:border-1 not-dark:border-slate-300 rounded px-2 py-1.5 w-full font-normal",
+                r#type: "text",
+                value: settings().author,
+                onchange: move |evt| {
+                    settings.write().author = evt.value();
+                },
+            }
+        }
+        label {
+            class: "text-sm font-medium dark:text-slate-50 text-slate-950 grid gap-1",
+            "Scoring model"
+            select {
+                class: "dark:bg-slate-700 bg-slate-200 not-dark:border-1 not-dark:border-slate-300 rounded px-2 py-1.5 w-full font-normal cursor-pointer",
+                onchange: move |evt| {
+                    if let Ok(model) = evt.value().parse() {
+                        settings.write().scoring_model = model;
+                    }
+                },
+                for model in ScoringModelKind::VARIANTS {
+                    option {
+                        value: "{model:?}",
+                        selected: *model == settings().scoring_model,
+                        "{model}"
+                    }
+                }
+            }
+        }
+        if settings().scoring_model == ScoringModelKind::WeightedDomains {
+            details {
+                class: "text-sm dark:text-slate-50 text-slate-950",
+                summary {
+                    class: "font-medium cursor-pointer",
+                    "Domain weights"
+                }
+                span {
+                    class: "block text-xs opacity-70 my-1",
+                    "Domains with weight 0 do not count towards the overall score"
+                }
+                for domain in Domain::VARIANTS {
+                    ThresholdInputComponent {
+                        key: "weight_{domain}",
+                        label: "{domain}",
+                        value: settings().domain_weights.weight(domain),
+                        max: 10.0,
+                        onchange: move |value| {
+                            settings.write().domain_weights.set_weight(*domain, value);
+                        },
+                    }
+                }
+            }
+        }
+        details {
+            class: "text-sm dark:text-slate-50 text-slate-950",
+            summary {
+                class: "font-medium cursor-pointer",
+                "Level thresholds"
+            }
+            span {
+                class: "block text-xs opacity-70 my-1",
+                "Minimum score to reach a level"
+            }
+            for (i , threshold) in settings().level_thresholds.maturity().iter().enumerate() {
+                ThresholdInputComponent {
+                    key: "maturity_{i}_{threshold}",
+                    label: "Maturity level {i + 1}",
+                    value: *threshold,
+                    max: 5.0,
+                    onchange: move |value| {
+                        let thresholds = settings().level_thresholds;
+                        let mut maturity = *thresholds.maturity();
+                        maturity[i] = value;
+                        if let Ok(thresholds) = LevelThresholds::new(maturity, *thresholds.capability()) {
+                            settings.write().level_thresholds = thresholds;
+                        }
+                    },
+                }
+            }
+            for (i , threshold) in settings().level_thresholds.capability().iter().enumerate() {
+                ThresholdInputComponent {
+                    key: "capability_{i}_{threshold}",
+                    label: "Capability level {i + 1}",
+                    value: *threshold,
+                    max: 3.0,
+                    onchange: move |value| {
+                        let thresholds = settings().level_thresholds;
+                        let mut capability = *thresholds.capability();
+                        capability[i] = value;
+                        if let Ok(thresholds) = LevelThresholds::new(*thresholds.maturity(), capability) {
+                            settings.write().level_thresholds = thresholds;
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ThresholdInputComponent(
+    label: String,
+    value: f64,
+    max: f64,
+    onchange: EventHandler<f64>,
+) -> Element {
+    rsx! {
+        label {
+            class: "flex justify-between items-center gap-2 mt-1",
+            "{label}"
+            input {
+                class: "dark:bg-slate-700 bg-slate-200 not-dark:border-1 not-dark:border-slate-300 rounded px-2 py-0.5 w-20",
+                r#type: "number",
+                step: "0.1",
+                min: "0",
+                max: "{max}",
+                value: "{value}",
+                onchange: move |evt| {
+                    if let Ok(value) = evt.value().parse::<f64>() {
+                        onchange.call(value);
+                    }
+                },
+            }
+        }
     }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use crate::{
     Route,
-    components::{ButtonComponent, DomainIconComponent, SidebarScoreComponent},
+    components::{ButtonComponent, DomainIconComponent, SearchComponent, SidebarScoreComponent},
     utils::{use_app_settings, use_schema, use_stats},
 };
 use cmm_core::{cid::Domain, score::Score};
@@ -112,6 +112,9 @@ pub fn SidebarComponent(children: Element) -> Element {
             }
             div {
                 class: "p-4 mb-14",
+                SearchComponent {
+                    onnavigate: move |_| sidebar_open.set(false),
+                }
                 div {
                     class: "mb-4",
                     Link {
@@ -120,6 +123,14 @@ pub fn SidebarComponent(children: Element) -> Element {
                         "Report"
                     }
                 }
+                div {
+                    class: "mb-4",
+                    Link {
+                        class: "text-lg font-semibold flex justify-between",
+                        to: Route::Actions {},
+                        "Action plan"
+                    }
+                }
                 NavigationSectionComponent {
                     title: "Overview",
                     href: "overview",
@@ -128,6 +139,18 @@ pub fn SidebarComponent(children: Element) -> Element {
                     title: "Pinned",
                     href: "pinned",
                 }
+                NavigationSectionComponent {
+                    title: "Evidence policy",
+                    href: "policy",
+                }
+                NavigationSectionComponent {
+                    title: "Consistency",
+                    href: "consistency",
+                }
+                NavigationSectionComponent {
+                    title: "Tags",
+                    href: "tags",
+                }
                 for domain in Domain::VARIANTS {
                     NavigationSectionComponent {
                         title: "{domain}",
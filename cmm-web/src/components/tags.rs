@@ -0,0 +1,97 @@
+use cmm_core::cid::CID;
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::fa_solid_icons::FaXmark};
+
+use crate::{components::ControlsListComponent, utils::use_soc_data};
+
+#[component]
+pub fn TagEditorComponent(cid: CID, tags: Vec<String>) -> Element {
+    let mut data = use_soc_data();
+
+    rsx! {
+        div {
+            class: "mt-4 flex flex-wrap items-center gap-2",
+            span {
+                class: "text-sm",
+                "Tags"
+            }
+            for tag in tags {
+                span {
+                    key: "{cid}_{tag}",
+                    class: "flex items-center gap-1 bg-blue-500 text-white py-0.5 px-2 rounded text-xs",
+                    "{tag}"
+                    button {
+                        class: "cursor-pointer print:hidden",
+                        title: "Remove tag",
+                        onclick: move |_| {
+                            data.write().remove_tag(&cid, &tag);
+                        },
+                        Icon {
+                            width: 10,
+                            height: 10,
+                            fill: "white",
+                            icon: FaXmark,
+                        }
+                    }
+                }
+            }
+            input {
+                class: "dark:bg-slate-700 bg-slate-200 not-dark:border-1 not-dark:border-slate-300 rounded px-2 py-0.5 text-xs print:hidden",
+                r#type: "text",
+                placeholder: "Add tag",
+                onchange: move |evt| {
+                    data.write().add_tag(&cid, &evt.value());
+                },
+            }
+        }
+    }
+}
+
+#[component]
+pub fn TagFilterComponent() -> Element {
+    let data = use_soc_data();
+
+    let mut selected = use_signal(|| None::<String>);
+
+    // Reset the filter if the selected tag no longer exists
+    use_effect(move || {
+        if let Some(tag) = selected()
+            && !data().tags().contains(&&tag)
+        {
+            selected.set(None);
+        }
+    });
+
+    if data().tags().is_empty() {
+        return rsx! {
+            div {
+                class: "opacity-60",
+                "No tags yet. Add tags to a control to filter by them."
+            }
+        };
+    }
+
+    rsx! {
+        div {
+            class: "flex flex-wrap gap-2 mb-4",
+            for tag in data().tags().into_iter().cloned() {
+                button {
+                    key: "{tag}",
+                    class: "py-1 px-2 rounded text-xs border-1 cursor-pointer",
+                    class: if selected().as_ref() == Some(&tag) { "bg-blue-500 border-blue-400 text-white" } else { "bg-slate-200 dark:bg-slate-700 border-slate-300 dark:border-slate-600 hover:bg-slate-300 dark:hover:bg-slate-600" },
+                    onclick: move |_| {
+                        if selected().as_ref() == Some(&tag) {
+                            selected.set(None);
+                        } else {
+                            selected.set(Some(tag.clone()));
+                        }
+                    },
+                    "{tag} ({data().controls_by_tag(&tag).count()})"
+                }
+            }
+        }
+        if let Some(tag) = selected() {
+            ControlsListComponent { pinned: false, tag }
+        }
+    }
+}
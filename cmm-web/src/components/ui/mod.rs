@@ -10,6 +10,7 @@ mod section_title;
 mod button;
 mod value_or_placeholder;
 mod print_box;
+mod stacked_bar;
 pub use toggle::ToggleComponent;
 pub use star_btn::StarButtonComponent;
 pub use small_btn::SmallButtonComponent;
@@ -19,3 +20,4 @@ pub use section_title::SectionTitleComponent;
 pub use button::ButtonComponent;
 pub use value_or_placeholder::ValueOrPlaceholderComponent;
 pub use print_box::PrintBoxComponent;
+pub use stacked_bar::{BarSegment, StackedBarComponent};
@@ -41,7 +41,16 @@ pub fn BadToGoodProgressBarComponent(
     height: Option<u32>,
     tooltip_prefix: Option<String>
 ) -> Element {
-    let percentage = score.as_percentage();
+    let Some(percentage) = score.as_percentage() else {
+        return rsx! {
+            ProgressBarComponent {
+                max: score.max(),
+                value: 0.0,
+                height,
+                tooltip: if tooltip_prefix.is_some() { "{tooltip_prefix.unwrap()} N/A" } else { "N/A" },
+            }
+        };
+    };
     let class = if percentage < 12.5 {
         "bg-red-600"
     } else if percentage < 25.0 {
@@ -62,7 +71,7 @@ pub fn BadToGoodProgressBarComponent(
     rsx! {
         ProgressBarComponent {
             max: score.max(),
-            value: score.score(),
+            value: score.score().unwrap_or_default(),
             height,
             class,
             tooltip: if tooltip_prefix.is_some() { "{tooltip_prefix.unwrap()} {round(percentage, 2)}%" } else { "{round(percentage, 2)}%" },
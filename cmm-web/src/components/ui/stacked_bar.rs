@@ -0,0 +1,37 @@
+use dioxus::prelude::*;
+
+use crate::utils::round;
+
+#[derive(PartialEq, Clone)]
+pub struct BarSegment {
+    pub value: u32,
+    pub class: &'static str,
+    pub label: String,
+}
+
+#[component]
+pub fn StackedBarComponent(segments: Vec<BarSegment>, height: Option<u32>) -> Element {
+    let total: u32 = segments.iter().map(|segment| segment.value).sum();
+
+    if total == 0 {
+        return rsx! {
+            div {
+                class: "h-{height.unwrap_or(2)} w-full rounded block dark:bg-blue-100 bg-gray-300",
+            }
+        };
+    }
+
+    rsx! {
+        div {
+            // Tailwind: h-1 h-2 h-3 h-4
+            class: "h-{height.unwrap_or(2)} w-full rounded flex overflow-hidden",
+            for segment in segments.iter().filter(|segment| segment.value > 0) {
+                div {
+                    class: "h-full {segment.class}",
+                    title: "{segment.label}: {segment.value}",
+                    width: "{round(segment.value as f64 / total as f64 * 100.0, 2)}%",
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,16 @@
-use cmm_core::{data::SOCData, schema::Schema, score::Stats};
+use cmm_core::{
+    data::SOCData,
+    level::LevelThresholds,
+    lint::Lint,
+    schema::{Dependencies, Recommendations, Schema},
+    score::Stats,
+    scoring::{ScoringModelKind, WeightedDomains},
+};
 use dioxus::prelude::*;
 
 use dioxus_sdk_storage::{LocalStorage, use_synced_storage};
 
-use crate::components::AppSettings;
+use crate::{components::AppSettings, utils::use_app_settings};
 
 use crate::Route;
 
@@ -14,6 +21,12 @@ pub fn SettingsLayout() -> Element {
         show_percentage: false,
         show_scores: true,
         show_comparison: false,
+        show_distribution: false,
+        author: String::new(),
+        level_thresholds: LevelThresholds::default(),
+        scoring_model: ScoringModelKind::default(),
+        domain_weights: WeightedDomains::default(),
+        conditional_controls: false,
     });
 
     let _ = use_context_provider(|| settings);
@@ -23,8 +36,19 @@ pub fn SettingsLayout() -> Element {
 
 #[component]
 pub fn DataSchemaLayout() -> Element {
+    // The recommendations don't change the scores, they are always added for the report
     let schema: Schema = use_context_provider(|| {
-        serde_json::from_str(include_str!("../../scheme-2.3.4.json")).unwrap()
+        let schema: Schema =
+            serde_json::from_str(include_str!("../../scheme-2.3.4.json")).unwrap();
+        let recommendations: Recommendations =
+            serde_json::from_str(include_str!("../../recommendations-2.3.4.json")).unwrap();
+        schema.with_recommendations(&recommendations)
+    });
+    let _: Lint = use_context_provider(|| {
+        serde_json::from_str(include_str!("../../lint-2.3.4.json")).unwrap()
+    });
+    let dependencies: Dependencies = use_context_provider(|| {
+        serde_json::from_str(include_str!("../../dependencies-2.3.4.json")).unwrap()
     });
 
     let data: Signal<SOCData> =
@@ -33,14 +57,31 @@ pub fn DataSchemaLayout() -> Element {
         use_synced_storage::<LocalStorage, _>("compare-cmm".to_owned(), || SOCData::from(&schema));
     let (data, cmp_data) = use_context_provider(|| (data, compare_data));
 
-    let stats = use_signal(|| Stats::new(data(), schema.clone()));
-    let cmp_stats = use_signal(|| Stats::new(cmp_data(), schema.clone()));
+    let settings = use_app_settings();
+
+    let stats = use_signal(|| stats_for(data(), &schema, &dependencies, &settings()));
+    let cmp_stats = use_signal(|| stats_for(cmp_data(), &schema, &dependencies, &settings()));
     let (mut stats, mut cmp_stats) = use_context_provider(|| (stats, cmp_stats));
 
     use_effect(move || {
-        stats.set(Stats::new(data(), schema.clone()));
-        cmp_stats.set(Stats::new(cmp_data(), schema.clone()));
+        let settings = settings();
+        stats.set(stats_for(data(), &schema, &dependencies, &settings));
+        cmp_stats.set(stats_for(cmp_data(), &schema, &dependencies, &settings));
     });
 
     rsx!(Outlet::<Route> {})
 }
+
+/// Stats with the scoring model and the opt-in dependencies selected in the settings
+fn stats_for(
+    data: SOCData,
+    schema: &Schema,
+    dependencies: &Dependencies,
+    settings: &AppSettings,
+) -> Stats {
+    let schema = match settings.conditional_controls {
+        true => schema.clone().with_dependencies(dependencies),
+        false => schema.clone(),
+    };
+    Stats::with_model(data, schema, settings.scoring_model.model(&settings.domain_weights))
+}
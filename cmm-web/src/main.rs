@@ -1,6 +1,6 @@
 use crate::{
     layouts::{DataSchemaLayout, SettingsLayout},
-    pages::{App, Report},
+    pages::{Actions, App, Report},
 };
 use dioxus::prelude::*;
 
@@ -62,4 +62,7 @@ pub enum Route {
     
             #[route("/report")]
             Report {},
+
+            #[route("/actions")]
+            Actions {},
 }
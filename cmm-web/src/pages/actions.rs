@@ -0,0 +1,33 @@
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::fa_solid_icons::FaArrowTurnDown};
+
+use crate::{Route, components::ActionPlanComponent};
+
+#[component]
+pub fn Actions() -> Element {
+    rsx! {
+        main {
+            class: "max-w-4xl mx-auto not-print:mt-16 px-4",
+            Link {
+                class: "print:hidden flex gap-x-2 items-center mb-4 hover:underline",
+                to: Route::App {  },
+                Icon {
+                    icon: FaArrowTurnDown,
+                    width: 14,
+                    height: 14,
+                    class: "rotate-z-90",
+                }
+                "Back"
+            }
+            h1 {
+                class: "font-semibold text-4xl mb-4",
+                "Action plan"
+            }
+            p {
+                class: "text-xl mb-4 print:hidden",
+                "All improvement actions, sorted by due date and domain."
+            }
+            ActionPlanComponent {  }
+        }
+    }
+}
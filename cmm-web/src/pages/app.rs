@@ -6,6 +6,7 @@ use crate::{
     components::{
         ChartComponent, ControlsListComponent, ImportExportComponent, OverviewComponent,
         SectionTitleComponent, SettingsComponent, SidebarComponent, StarButtonComponent,
+        TagFilterComponent, LintFindingsComponent, PolicyComponent,
     },
     utils::use_soc_data,
 };
@@ -42,6 +43,18 @@ pub fn App() -> Element {
                 div { class: "pinned-list",
                     ControlsListComponent { pinned: true }
                 }
+                div { class: "mt-16",
+                    SectionTitleComponent { id: "policy", text: "Evidence policy" }
+                    PolicyComponent {}
+                }
+                div { class: "mt-16",
+                    SectionTitleComponent { id: "consistency", text: "Consistency" }
+                    LintFindingsComponent {}
+                }
+                div { class: "mt-16",
+                    SectionTitleComponent { id: "tags", text: "Tags" }
+                    TagFilterComponent {}
+                }
                 div { class: "mt-16",
                     ControlsListComponent { pinned: false }
                 }
@@ -1,5 +1,6 @@
-
+mod actions;
 mod app;
 mod report;
+pub use actions::Actions;
 pub use app::App;
 pub use report::Report;
@@ -3,7 +3,10 @@ use dioxus_free_icons::{Icon, icons::fa_solid_icons::FaArrowTurnDown};
 
 use crate::{
     Route,
-    components::{PrintOverviewComponent, ProfileComponent, ProfileValuesComponent},
+    components::{
+        PrintOverviewComponent, PrintRecommendationsComponent, ProfileComponent,
+        ProfileValuesComponent,
+    },
 };
 
 #[component]
@@ -57,6 +60,14 @@ pub fn Report() -> Element {
                         }
                         PrintOverviewComponent {  }
                     }
+                    section {
+                        class: "grid gap-4 break-before-page",
+                        h3 {
+                            class: "text-2xl",
+                            "Recommendations"
+                        }
+                        PrintRecommendationsComponent {  }
+                    }
                 }
             }
         }
@@ -1,4 +1,9 @@
-use cmm_core::{data::SOCData, schema::Schema, score::Stats};
+use cmm_core::{
+    data::SOCData,
+    lint::Lint,
+    schema::{Dependencies, Schema},
+    score::Stats,
+};
 use dioxus::{hooks::use_context, signals::Signal};
 
 use crate::components::AppSettings;
@@ -31,6 +36,14 @@ pub fn use_schema() -> Schema {
     use_context::<Schema>()
 }
 
+pub fn use_lint() -> Lint {
+    use_context::<Lint>()
+}
+
+pub fn use_dependencies() -> Dependencies {
+    use_context::<Dependencies>()
+}
+
 pub fn use_stats() -> (Signal<Stats>, Signal<Stats>) {
     use_context::<(Signal<Stats>, Signal<Stats>)>()
 }